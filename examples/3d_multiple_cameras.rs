@@ -7,19 +7,9 @@ fn main() {
     App::new()
         .add_plugins((DefaultPlugins, PrankPlugin::default()))
         .add_systems(Startup, setup)
-        .add_systems(Update, camera_switch)
         .run();
 }
 
-#[derive(Component)]
-struct GameCamera;
-
-#[derive(Component)]
-struct FrontView;
-
-#[derive(Component)]
-struct TopView;
-
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -50,90 +40,55 @@ fn setup(
         },
     ));
 
-    commands.spawn((
-        Name::new("GameCamera"),
-        GameCamera,
-        Camera3dBundle {
-            transform: Transform::from_xyz(0.0, 2.0, 0.0),
-            ..default()
-        },
-    ));
+    let game_camera = commands
+        .spawn((
+            Name::new("GameCamera"),
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 2.0, 0.0),
+                ..default()
+            },
+        ))
+        .id();
 
-    commands.spawn((
-        Name::new("FrontView"),
-        FrontView,
-        Prank3d {
-            is_active: false,
-            ..default()
-        },
-        Camera3dBundle {
-            transform: Transform::from_xyz(0.0, 1.0, 0.0)
-                .looking_at(Vec3::new(0.0, 0.5, -8.0), Vec3::Y),
-            camera: Camera {
+    let front_view = commands
+        .spawn((
+            Name::new("FrontView"),
+            Prank3d {
                 is_active: false,
                 ..default()
             },
-            ..default()
-        },
-    ));
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 1.0, 0.0)
+                    .looking_at(Vec3::new(0.0, 0.5, -8.0), Vec3::Y),
+                camera: Camera {
+                    is_active: false,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
 
-    commands.spawn((
-        Name::new("TopView"),
-        TopView,
-        Prank3d {
-            is_active: false,
-            ..default()
-        },
-        Camera3dBundle {
-            transform: Transform::from_xyz(0.0, 5.0, -5.0)
-                .looking_at(Vec3::new(0.0, 0.5, -8.0), Vec3::Y),
-            camera: Camera {
+    let top_view = commands
+        .spawn((
+            Name::new("TopView"),
+            Prank3d {
                 is_active: false,
                 ..default()
             },
-            ..default()
-        },
-    ));
-}
-
-fn camera_switch(
-    mut game_camera: Query<&mut Camera, With<GameCamera>>,
-    mut front_view: Query<(&mut Camera, &mut Prank3d), (With<FrontView>, Without<GameCamera>)>,
-    mut top_view: Query<
-        (&mut Camera, &mut Prank3d),
-        (With<TopView>, Without<GameCamera>, Without<FrontView>),
-    >,
-    keyboard: Res<Input<KeyCode>>,
-) {
-    let mut gc_camera = game_camera.single_mut();
-    let (mut fv_camera, mut fv_prank) = front_view.single_mut();
-    let (mut tv_camera, mut tv_prank) = top_view.single_mut();
-
-    if keyboard.just_pressed(KeyCode::Key1) {
-        gc_camera.is_active = true;
-
-        fv_camera.is_active = false;
-        fv_prank.is_active = false;
-
-        tv_camera.is_active = false;
-        tv_prank.is_active = false;
-    }
-    if keyboard.just_pressed(KeyCode::Key2) {
-        gc_camera.is_active = false;
-
-        fv_camera.is_active = true;
-        fv_prank.is_active = true;
-
-        tv_camera.is_active = false;
-        tv_prank.is_active = false;
-    }
-    if keyboard.just_pressed(KeyCode::Key3) {
-        gc_camera.is_active = false;
-
-        fv_camera.is_active = false;
-        fv_prank.is_active = false;
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 5.0, -5.0)
+                    .looking_at(Vec3::new(0.0, 0.5, -8.0), Vec3::Y),
+                camera: Camera {
+                    is_active: false,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
 
-        tv_camera.is_active = true;
-        tv_prank.is_active = true;
-    }
+    let mut switcher = Prank3dSwitcher::new(vec![game_camera, front_view, top_view]);
+    switcher.transition_duration = 0.5;
+    commands.insert_resource(switcher);
 }
@@ -1,7 +1,10 @@
 #![allow(clippy::type_complexity)]
 #![doc = include_str!("../README.md")]
 
-use self::three::{gizmo::Prank3dGizmoConfig, hud::Prank3dHudConfig, Prank3dPlugin};
+use self::three::{
+    gizmo::Prank3dGizmoConfig, hud::Prank3dHudConfig, pip::Prank3dPipConfig,
+    possess::Prank3dPossessConfig, screenshot::Prank3dScreenshotConfig, Prank3dPlugin,
+};
 use bevy::prelude::*;
 
 pub mod prelude;
@@ -34,6 +37,8 @@ impl Plugin for PrankPlugin {
 
 /// Configuration of [`PrankPlugin`].
 ///
+/// It is inserted as a resource, and changes to it take effect immediately.
+///
 /// # Example
 ///
 /// ```no_run
@@ -62,6 +67,21 @@ pub struct PrankConfig {
     ///
     /// Set `None` to disable.
     pub hud3d: Option<Prank3dHudConfig>,
+
+    /// Three-dimensional picture-in-picture camera preview configuration.
+    ///
+    /// Set `None` to disable.
+    pub pip3d: Option<Prank3dPipConfig>,
+
+    /// Three-dimensional camera possess and eject configuration.
+    ///
+    /// Set `None` to disable.
+    pub possess3d: Option<Prank3dPossessConfig>,
+
+    /// Three-dimensional camera screenshot configuration.
+    ///
+    /// Disabled by default, set `Some` to enable.
+    pub screenshot3d: Option<Prank3dScreenshotConfig>,
 }
 
 impl Default for PrankConfig {
@@ -69,6 +89,9 @@ impl Default for PrankConfig {
         Self {
             gizmo3d: Some(Prank3dGizmoConfig::default()),
             hud3d: Some(Prank3dHudConfig::default()),
+            pip3d: Some(Prank3dPipConfig::default()),
+            possess3d: Some(Prank3dPossessConfig::default()),
+            screenshot3d: None,
         }
     }
 }
@@ -1,6 +1,21 @@
 //! Re-exports common resources, components, etc.
 
 pub use crate::{
-    three::{gizmo::Prank3dGizmoConfig, hud::Prank3dHudConfig, Prank3d},
+    three::{
+        gizmo::{
+            Prank3dGizmoAppExt, Prank3dGizmoColor, Prank3dGizmoConfig, Prank3dGizmoHidden,
+            Prank3dGridPlane,
+        },
+        hud::{
+            Prank3dHudAnchor, Prank3dHudAppExt, Prank3dHudConfig, Prank3dHudLayout,
+            Prank3dHudSegment,
+        },
+        pip::{Prank3dPipConfig, Prank3dPipCorner},
+        possess::Prank3dPossessConfig,
+        screenshot::Prank3dScreenshotConfig,
+        switcher::Prank3dSwitcher,
+        transition::{Prank3dEasing, Prank3dPose, Prank3dTransitionFinished},
+        Prank3d, Prank3dLookCurve, Prank3dPanDepth,
+    },
     PrankConfig, PrankPlugin,
 };
@@ -1,38 +1,82 @@
 //! Provides three-dimensional camera functionality.
 
 use self::{
+    clipboard::Prank3dClipboardPlugin,
+    collision::{collide, Prank3dColliderBackend, Prank3dCollisionPlugin},
     gizmo::Prank3dGizmoPlugin,
     hud::Prank3dHudPlugin,
+    pick::Prank3dPickPlugin,
+    pip::Prank3dPipPlugin,
+    possess::Prank3dPossessPlugin,
+    screenshot::Prank3dScreenshotPlugin,
     state::{any_active, Prank3dActive, Prank3dState, Prank3dStatePlugin},
+    switcher::Prank3dSwitcherPlugin,
+    transition::{Prank3dTransition, Prank3dTransitionPlugin},
+    walk::{fall, walk, Prank3dWalkPlugin},
 };
 use bevy::{
     input::mouse::{MouseMotion, MouseWheel},
     prelude::*,
+    render::camera::RenderTarget,
+    window::{PrimaryWindow, WindowRef},
 };
 use std::f32::consts;
 
+mod clipboard;
+pub mod collision;
 pub mod gizmo;
 pub mod hud;
+pub mod pick;
+pub mod pip;
+pub mod possess;
+pub mod screenshot;
 mod state;
+pub mod switcher;
+pub mod transition;
+mod walk;
 
 pub(super) struct Prank3dPlugin;
 
 impl Plugin for Prank3dPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((Prank3dGizmoPlugin, Prank3dHudPlugin, Prank3dStatePlugin))
-            .register_type::<Prank3d>()
-            .add_systems(
-                Update,
+        app.add_plugins((
+            Prank3dClipboardPlugin,
+            Prank3dCollisionPlugin,
+            Prank3dGizmoPlugin,
+            Prank3dHudPlugin,
+            Prank3dPickPlugin,
+            Prank3dPipPlugin,
+            Prank3dPossessPlugin,
+            Prank3dScreenshotPlugin,
+            Prank3dStatePlugin,
+            Prank3dSwitcherPlugin,
+            Prank3dTransitionPlugin,
+            Prank3dWalkPlugin,
+        ))
+        .register_type::<Prank3d>()
+        .register_type::<Prank3dPanDepth>()
+        .register_type::<Prank3dLookCurve>()
+        .init_resource::<PanDepth>()
+        .init_resource::<PendingLook>()
+        .add_systems(OnEnter(Prank3dState::Offset), measure_pan_depth)
+        .add_systems(OnEnter(Prank3dState::None), reset_pending_look)
+        .add_systems(
+            Update,
+            (
+                initialize,
                 (
-                    initialize,
                     (
-                        interpolation,
                         fly.run_if(in_state(Prank3dState::Fly)),
                         offset.run_if(in_state(Prank3dState::Offset)),
-                    )
-                        .run_if(any_active),
-                ),
-            );
+                        (walk, fall).chain().run_if(in_state(Prank3dState::Walk)),
+                    ),
+                    collide,
+                    interpolation,
+                )
+                    .chain()
+                    .run_if(any_active),
+            ),
+        );
     }
 }
 
@@ -71,6 +115,18 @@ pub struct Prank3d {
     /// Scalar of `speed` field to adjust during gameplay with [`MouseWheel`].
     pub speed_scalar: f32,
 
+    /// Key to hold for moving `boost_scalar` times faster.
+    pub boost_key: KeyCode,
+
+    /// Scalar of `speed` field applied while `boost_key` is held.
+    pub boost_scalar: f32,
+
+    /// Key to hold for moving `precision_scalar` times slower.
+    pub precision_key: KeyCode,
+
+    /// Scalar of `speed` field applied while `precision_key` is held.
+    pub precision_scalar: f32,
+
     /// The rate that the [`Camera`] approaches its translation.
     ///
     /// Values closer to zero make the approaching faster.
@@ -81,14 +137,90 @@ pub struct Prank3d {
     /// If its not in range `[0.0, 1.0)`.
     pub lerp_rate: f32,
 
-    /// Sensitivity of [`MouseMotion`].
+    /// Whether the [`Camera`] collides with the world instead of flying through it.
+    ///
+    /// See [`collision`] for how collision is resolved.
+    pub collision: bool,
+
+    /// Radius of the sphere that represents the [`Camera`] while `collision` is enabled.
+    pub collision_radius: f32,
+
+    /// Key to toggle `collision` with.
+    pub collision_key: KeyCode,
+
+    /// Key to toggle walk mode with.
+    ///
+    /// In walk mode the [`Camera`] follows the ground found by the
+    /// [`Prank3dCollider`](collision::Prank3dCollider) at `eye_height`, and moves in the horizontal
+    /// plane only.
+    pub walk_key: KeyCode,
+
+    /// Constant speed that the [`Camera`] walks at.
+    pub walk_speed: f32,
+
+    /// Height of the [`Camera`] above the ground in walk mode.
+    pub eye_height: f32,
+
+    /// Downward acceleration applied to the [`Camera`] in walk mode.
+    pub gravity: f32,
+
+    /// Upward speed that the [`Camera`] jumps at in walk mode.
+    pub jump_speed: f32,
+
+    /// Scalar of the rate that the [`Camera`] pans at in offset mode.
+    ///
+    /// With `1.0`, content at `pan_depth` moves exactly as much as the [`MouseMotion`].
+    pub pan_speed: f32,
+
+    /// Depth of the content that determines the rate that the [`Camera`] pans at in offset mode.
+    ///
+    /// Ignored for [`OrthographicProjection`], since its rate is independent of depth.
+    pub pan_depth: Prank3dPanDepth,
+
+    /// Mouse button to pick entities under the cursor with.
+    ///
+    /// See [`pick`] for how picked entities are reported.
+    pub pick_button: MouseButton,
+
+    /// Whether picking should test against mesh triangles in addition to their [`Aabb`].
+    ///
+    /// [`Aabb`]: bevy::render::primitives::Aabb
+    pub pick_precise: bool,
+
+    /// Key to copy a [`Transform`] snippet of the current pose with.
+    ///
+    /// The snippet is written to the clipboard with the `clipboard` feature, or logged otherwise.
+    pub copy_pose_key: KeyCode,
+
+    /// Radians that the [`Camera`] rotates per pixel of [`MouseMotion`].
     pub sensitivity: Vec2,
 
+    /// Whether vertical [`MouseMotion`] should be inverted.
+    pub invert_y: bool,
+
+    /// The rate that the [`Camera`] approaches its rotation.
+    ///
+    /// Values closer to zero make the approaching faster.
+    /// Zero disables smoothing.
+    ///
+    /// # Panic
+    ///
+    /// If its not in range `[0.0, 1.0)`.
+    pub look_smoothing: f32,
+
+    /// Curve that maps [`MouseMotion`] speed to rotation.
+    pub look_curve: Prank3dLookCurve,
+
     /// The current translation that the camera approaches towards.
     ///
     /// This should be used instead of [`Transform`]'s `translation` field, with the exception of
     /// initializing the [`Transform`] component.
     pub translation: Vec3,
+
+    /// The transition that is in progress.
+    ///
+    /// See [`Prank3d::start_transition`] for how to start one.
+    pub transition: Option<Prank3dTransition>,
 }
 
 impl Default for Prank3d {
@@ -97,18 +229,107 @@ impl Default for Prank3d {
             is_active: true,
             speed: 25.0,
             speed_scalar: 1.0,
+            boost_key: KeyCode::ControlLeft,
+            boost_scalar: 4.0,
+            precision_key: KeyCode::AltLeft,
+            precision_scalar: 0.25,
             lerp_rate: 0.001,
-            sensitivity: Vec2::splat(0.08),
+            collision: false,
+            collision_radius: 0.25,
+            collision_key: KeyCode::C,
+            walk_key: KeyCode::V,
+            walk_speed: 5.0,
+            eye_height: 1.7,
+            gravity: 9.81,
+            jump_speed: 5.0,
+            pan_speed: 1.0,
+            pan_depth: Prank3dPanDepth::Cursor {
+                fallback_depth: 10.0,
+            },
+            pick_button: MouseButton::Left,
+            pick_precise: false,
+            copy_pose_key: KeyCode::P,
+            sensitivity: Vec2::splat(0.0013),
+            invert_y: false,
+            look_smoothing: 0.0,
+            look_curve: Prank3dLookCurve::Linear,
             translation: Vec3::ZERO,
+            transition: None,
         }
     }
 }
 
+/// Depth of the content that [`Prank3d`] pans relative to in offset mode.
+#[derive(Clone, Copy, Reflect)]
+pub enum Prank3dPanDepth {
+    /// Content is always at a constant depth.
+    Reference {
+        /// Distance from the [`Camera`] along its forward axis.
+        depth: f32,
+    },
+
+    /// Content is under the cursor once offset mode begins, as found by the
+    /// [`Prank3dCollider`](collision::Prank3dCollider).
+    Cursor {
+        /// Distance from the [`Camera`] along its forward axis if nothing is under the cursor.
+        fallback_depth: f32,
+    },
+}
+
+/// Curve that maps [`MouseMotion`] speed to [`Prank3d`] rotation.
+#[derive(Clone, Copy, Reflect)]
+pub enum Prank3dLookCurve {
+    /// Rotation is proportional to [`MouseMotion`].
+    Linear,
+
+    /// Rotation is scaled by `1.0 + acceleration * speed`, where `speed` is in pixels per second.
+    Accelerated {
+        /// The amount that each pixel per second of speed adds to the scalar.
+        acceleration: f32,
+
+        /// The maximum scalar that rotation is multiplied by.
+        max_scalar: f32,
+    },
+}
+
+impl Prank3dLookCurve {
+    /// Returns the scalar of [`MouseMotion`] moving at `speed` pixels per second.
+    fn scalar(&self, speed: f32) -> f32 {
+        match *self {
+            Self::Linear => 1.0,
+            Self::Accelerated {
+                acceleration,
+                max_scalar,
+            } => (1.0 + acceleration * speed).min(max_scalar),
+        }
+    }
+}
+
+impl Prank3d {
+    /// Returns the product of the speed modifiers whose keys are currently held.
+    fn speed_modifier(&self, keyboard: &Input<KeyCode>) -> f32 {
+        let mut modifier = 1.0;
+        if keyboard.pressed(self.boost_key) {
+            modifier *= self.boost_scalar;
+        }
+        if keyboard.pressed(self.precision_key) {
+            modifier *= self.precision_scalar;
+        }
+
+        modifier
+    }
+}
+
 fn initialize(mut pranks: Query<(&mut Prank3d, &Transform), Added<Prank3d>>) {
     for (mut prank, transform) in pranks.iter_mut() {
         if !(0.0..1.0).contains(&prank.lerp_rate) {
             panic!("`lerp_rate` field of `bevy_prank::three::Prank3d` must be in range [0.0, 1.0)");
         }
+        if !(0.0..1.0).contains(&prank.look_smoothing) {
+            panic!(
+                "`look_smoothing` field of `bevy_prank::three::Prank3d` must be in range [0.0, 1.0)"
+            );
+        }
 
         prank.translation = transform.translation;
     }
@@ -116,10 +337,15 @@ fn initialize(mut pranks: Query<(&mut Prank3d, &Transform), Added<Prank3d>>) {
 
 fn interpolation(
     active: Res<Prank3dActive>,
+    state: Res<State<Prank3dState>>,
     mut pranks: Query<(&mut Transform, &Prank3d)>,
     time: Res<Time>,
 ) {
     let (mut transform, prank) = pranks.get_mut(active.expect("is active")).expect("exists");
+    if matches!(**state, Prank3dState::Offset | Prank3dState::Walk) {
+        transform.translation = prank.translation;
+        return;
+    }
 
     transform.translation = transform.translation.lerp(
         prank.translation,
@@ -134,6 +360,7 @@ fn fly(
     mut motion: EventReader<MouseMotion>,
     mut wheel: EventReader<MouseWheel>,
     keyboard: Res<Input<KeyCode>>,
+    mut pending_look: ResMut<PendingLook>,
 ) {
     let (mut transform, mut prank) = pranks.get_mut(active.expect("is active")).expect("exists");
     let motion = motion.iter().fold(Vec2::ZERO, |acc, m| acc + m.delta);
@@ -163,29 +390,231 @@ fn fly(
 
     prank.speed_scalar = (prank.speed_scalar + 0.1 * wheel).clamp(0.1, 10.0);
 
-    let speed = prank.speed_scalar.powi(2) * prank.speed;
+    let speed = prank.speed_modifier(&keyboard) * prank.speed_scalar.powi(2) * prank.speed;
     prank.translation += speed * movement.normalize_or_zero() * time.delta_seconds();
 
+    look(
+        &mut transform,
+        &prank,
+        &mut pending_look,
+        motion,
+        time.delta_seconds(),
+    );
+}
+
+/// [`MouseMotion`] that is yet to be applied due to `look_smoothing` field of [`Prank3d`].
+#[derive(Default, Resource)]
+struct PendingLook(Vec2);
+
+fn reset_pending_look(mut pending_look: ResMut<PendingLook>) {
+    pending_look.0 = Vec2::ZERO;
+}
+
+/// Rotates `transform` by mouse `motion` with respect to `prank` look settings.
+///
+/// [`MouseMotion`] is already accumulated per frame, so it is not scaled by `delta_seconds`,
+/// which is only used for speed of the curve and smoothing.
+fn look(
+    transform: &mut Transform,
+    prank: &Prank3d,
+    pending_look: &mut PendingLook,
+    motion: Vec2,
+    delta_seconds: f32,
+) {
+    let speed = if delta_seconds > 0.0 {
+        motion.length() / delta_seconds
+    } else {
+        0.0
+    };
+    let mut motion = prank.look_curve.scalar(speed) * motion;
+    if prank.invert_y {
+        motion.y = -motion.y;
+    }
+
+    pending_look.0 += motion;
+    let motion = pending_look.0 * (1.0 - prank.look_smoothing.powf(delta_seconds));
+    pending_look.0 -= motion;
+
     let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
     transform.rotation = Quat::from_euler(
         EulerRot::YXZ,
-        yaw - prank.sensitivity.x * motion.x * time.delta_seconds(),
-        (pitch - prank.sensitivity.y * motion.y * time.delta_seconds())
-            .clamp(-consts::FRAC_PI_3, consts::FRAC_PI_3),
+        yaw - prank.sensitivity.x * motion.x,
+        (pitch - prank.sensitivity.y * motion.y).clamp(-consts::FRAC_PI_3, consts::FRAC_PI_3),
         0.0,
     );
 }
 
+/// Depth that offset mode pans relative to, measured once it begins.
+#[derive(Default, Resource)]
+struct PanDepth(f32);
+
+fn measure_pan_depth(world: &mut World) {
+    let entity = world.resource::<Prank3dActive>().expect("is active");
+    let depth = match world.get::<Prank3d>(entity).expect("exists").pan_depth {
+        Prank3dPanDepth::Reference { depth } => depth,
+        Prank3dPanDepth::Cursor { fallback_depth } => {
+            cursor_depth(world, entity).unwrap_or(fallback_depth)
+        }
+    };
+
+    world.resource_mut::<PanDepth>().0 = depth;
+}
+
+/// Returns depth of the content under the cursor of the `entity` [`Camera`].
+fn cursor_depth(world: &mut World, entity: Entity) -> Option<f32> {
+    let cursor = cursor_position(world, entity)?;
+    let camera = world.get::<Camera>(entity)?;
+    let transform = world.get::<GlobalTransform>(entity)?;
+    let ray = camera.viewport_to_world(transform, cursor)?;
+    let hit =
+        world
+            .resource::<Prank3dColliderBackend>()
+            .cast_ray(world, ray, f32::INFINITY, entity)?;
+
+    Some((ray.get_point(hit.distance) - transform.translation()).dot(transform.forward()))
+}
+
+/// Returns position of the cursor relative to the viewport of the `entity` [`Camera`].
+fn cursor_position(world: &mut World, entity: Entity) -> Option<Vec2> {
+    let camera = world.get::<Camera>(entity)?;
+    let viewport = camera.logical_viewport_rect()?;
+    let RenderTarget::Window(winref) = camera.target else {
+        return None;
+    };
+
+    let window = match winref {
+        WindowRef::Primary => world
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .get_single(world)
+            .ok()?,
+        WindowRef::Entity(entity) => world.get::<Window>(entity)?,
+    };
+
+    Some(window.cursor_position()? - viewport.min)
+}
+
 fn offset(
     active: Res<Prank3dActive>,
-    mut pranks: Query<(&mut Transform, &mut Prank3d)>,
-    time: Res<Time>,
+    mut pranks: Query<(&Transform, &Camera, &Projection, &mut Prank3d)>,
+    pan_depth: Res<PanDepth>,
     mut motion: EventReader<MouseMotion>,
 ) {
-    let (mut transform, mut prank) = pranks.get_mut(active.expect("is active")).expect("exists");
+    let (transform, camera, projection, mut prank) =
+        pranks.get_mut(active.expect("is active")).expect("exists");
     let motion = motion.iter().fold(Vec2::ZERO, |acc, m| acc + m.delta);
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    let units_per_pixel = match projection {
+        Projection::Perspective(projection) => {
+            2.0 * pan_depth.0 * (0.5 * projection.fov).tan() / viewport_size.y
+        }
+        Projection::Orthographic(projection) => projection.area.height() / viewport_size.y,
+    };
 
     let r = transform.rotation;
-    transform.translation += r * Vec3::new(motion.x, -motion.y, 0.0) * time.delta_seconds();
-    prank.translation = transform.translation;
+    prank.translation = transform.translation
+        + prank.pan_speed * units_per_pixel * (r * Vec3::new(motion.x, -motion.y, 0.0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rotates by the same total [`MouseMotion`] over one second at `fps`, then lets smoothing
+    /// settle.
+    fn simulate(prank: &Prank3d, fps: u32) -> Quat {
+        let mut transform = Transform::default();
+        let mut pending_look = PendingLook::default();
+        let delta_seconds = 1.0 / fps as f32;
+        let motion = Vec2::new(300.0, -120.0) / fps as f32;
+
+        for _ in 0..fps {
+            look(
+                &mut transform,
+                prank,
+                &mut pending_look,
+                motion,
+                delta_seconds,
+            );
+        }
+        for _ in 0..10 * fps {
+            look(
+                &mut transform,
+                prank,
+                &mut pending_look,
+                Vec2::ZERO,
+                delta_seconds,
+            );
+        }
+
+        transform.rotation
+    }
+
+    fn assert_frame_rate_independent(prank: Prank3d) {
+        let at_30 = simulate(&prank, 30);
+        let at_144 = simulate(&prank, 144);
+
+        assert_ne!(at_30, Quat::IDENTITY);
+        assert!(
+            at_30.abs_diff_eq(at_144, 1e-4),
+            "{:?} at 30 FPS differs from {:?} at 144 FPS",
+            at_30,
+            at_144,
+        );
+    }
+
+    #[test]
+    fn look_linear_is_frame_rate_independent() {
+        assert_frame_rate_independent(Prank3d::default());
+    }
+
+    #[test]
+    fn look_accelerated_is_frame_rate_independent() {
+        assert_frame_rate_independent(Prank3d {
+            look_curve: Prank3dLookCurve::Accelerated {
+                acceleration: 0.002,
+                max_scalar: 3.0,
+            },
+            ..default()
+        });
+    }
+
+    #[test]
+    fn look_smoothed_is_frame_rate_independent() {
+        assert_frame_rate_independent(Prank3d {
+            look_smoothing: 0.01,
+            ..default()
+        });
+    }
+
+    #[test]
+    fn look_accelerated_and_smoothed_is_frame_rate_independent() {
+        assert_frame_rate_independent(Prank3d {
+            look_smoothing: 0.01,
+            look_curve: Prank3dLookCurve::Accelerated {
+                acceleration: 0.002,
+                max_scalar: 3.0,
+            },
+            ..default()
+        });
+    }
+
+    #[test]
+    fn look_inverts_y() {
+        let rotation = simulate(&Prank3d::default(), 60);
+        let inverted = simulate(
+            &Prank3d {
+                invert_y: true,
+                ..default()
+            },
+            60,
+        );
+
+        let (yaw, pitch, _) = rotation.to_euler(EulerRot::YXZ);
+        let (inverted_yaw, inverted_pitch, _) = inverted.to_euler(EulerRot::YXZ);
+        assert!((yaw - inverted_yaw).abs() < 1e-5);
+        assert!((pitch + inverted_pitch).abs() < 1e-5);
+    }
 }
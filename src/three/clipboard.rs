@@ -0,0 +1,87 @@
+//! Provides copying the pose of three-dimensional cameras.
+
+use super::{any_active, Prank3d, Prank3dActive};
+use bevy::prelude::*;
+
+pub(super) struct Prank3dClipboardPlugin;
+
+impl Plugin for Prank3dClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "clipboard")]
+        app.init_non_send_resource::<Clipboard>();
+
+        app.add_systems(Update, copy_pose.run_if(any_active));
+    }
+}
+
+/// System clipboard that is kept open for the lifetime of the app, since contents written to it
+/// are lost as soon as it is closed on X11 and Wayland.
+#[cfg(feature = "clipboard")]
+#[derive(Default)]
+struct Clipboard(Option<arboard::Clipboard>);
+
+#[cfg(feature = "clipboard")]
+impl Clipboard {
+    /// Writes `text` to the clipboard, opening it first if needed.
+    fn set_text(&mut self, text: &str) -> bool {
+        if self.0.is_none() {
+            self.0 = arboard::Clipboard::new()
+                .map_err(|err| warn!("failed to open clipboard: {}", err))
+                .ok();
+        }
+
+        self.0
+            .as_mut()
+            .is_some_and(|clipboard| clipboard.set_text(text).is_ok())
+    }
+}
+
+fn copy_pose(
+    active: Res<Prank3dActive>,
+    pranks: Query<(&Transform, &Prank3d)>,
+    keyboard: Res<Input<KeyCode>>,
+    #[cfg(feature = "clipboard")] mut clipboard: NonSendMut<Clipboard>,
+) {
+    let (transform, prank) = pranks.get(active.expect("is active")).expect("exists");
+    if !keyboard.just_pressed(prank.copy_pose_key) {
+        return;
+    }
+
+    let snippet = pose_snippet(prank.translation, transform.rotation);
+
+    #[cfg(feature = "clipboard")]
+    let copied = clipboard.set_text(&snippet);
+    #[cfg(not(feature = "clipboard"))]
+    let copied = false;
+
+    if copied {
+        info!("copied pose to clipboard: {}", snippet);
+    } else {
+        info!("pose: {}", snippet);
+    }
+}
+
+/// Formats `translation` and `rotation` as a [`Transform`] expression.
+fn pose_snippet(translation: Vec3, rotation: Quat) -> String {
+    let Vec3 { x, y, z } = translation;
+    let [qx, qy, qz, qw] = rotation.to_array();
+    format!(
+        "Transform::from_xyz({:?}, {:?}, {:?}).with_rotation(Quat::from_xyzw({:?}, {:?}, {:?}, {:?}))",
+        x, y, z, qx, qy, qz, qw,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pose_snippet_is_transform_expression() {
+        let snippet = pose_snippet(Vec3::new(1.0, -2.5, 0.0), Quat::IDENTITY);
+
+        assert_eq!(
+            snippet,
+            "Transform::from_xyz(1.0, -2.5, 0.0).with_rotation(Quat::from_xyzw(0.0, 0.0, 0.0, 1.0))",
+        );
+    }
+}
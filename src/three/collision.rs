@@ -0,0 +1,628 @@
+//! Provides three-dimensional camera collision.
+
+use super::{any_active, pick::cast_mesh, Prank3d, Prank3dActive};
+use bevy::{prelude::*, render::primitives::Aabb};
+use std::ops::Deref;
+
+/// Maximum number of surfaces that the [`Camera`] slides along in a single frame.
+const MAX_SLIDES: usize = 4;
+
+/// Distance that the [`Camera`] is kept away from surfaces it collides with.
+const SKIN_WIDTH: f32 = 0.01;
+
+pub(super) struct Prank3dCollisionPlugin;
+
+impl Plugin for Prank3dCollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Prank3dColliderBackend>()
+            .add_systems(Update, toggle.run_if(any_active));
+    }
+}
+
+/// Backend that casts against the world to resolve [`Prank3d`] collision.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::three::collision::{Prank3dCollider, Prank3dColliderBackend, Prank3dHit};
+/// #
+/// struct FloorCollider;
+///
+/// impl Prank3dCollider for FloorCollider {
+///     fn cast_sphere(
+///         &self,
+///         _world: &World,
+///         ray: Ray,
+///         max_distance: f32,
+///         radius: f32,
+///         _exclude: Entity,
+///     ) -> Option<Prank3dHit> {
+///         let distance = ray.intersect_plane(Vec3::Y * radius, Vec3::Y)?;
+///         (distance <= max_distance).then_some(Prank3dHit {
+///             entity: Entity::PLACEHOLDER,
+///             distance,
+///             normal: Vec3::Y,
+///         })
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.insert_resource(Prank3dColliderBackend::new(FloorCollider));
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+pub trait Prank3dCollider: Send + Sync + 'static {
+    /// Sweeps a sphere of `radius` along `ray` and returns the closest hit within
+    /// `max_distance`.
+    ///
+    /// Hits with `exclude`, which is the [`Prank3d`] entity itself, must be ignored.
+    fn cast_sphere(
+        &self,
+        world: &World,
+        ray: Ray,
+        max_distance: f32,
+        radius: f32,
+        exclude: Entity,
+    ) -> Option<Prank3dHit>;
+
+    /// Casts `ray` and returns the closest hit within `max_distance`.
+    ///
+    /// Hits with `exclude`, which is the [`Prank3d`] entity itself, must be ignored.
+    fn cast_ray(
+        &self,
+        world: &World,
+        ray: Ray,
+        max_distance: f32,
+        exclude: Entity,
+    ) -> Option<Prank3dHit> {
+        self.cast_sphere(world, ray, max_distance, 0.0, exclude)
+    }
+}
+
+/// Result of a successful [`Prank3dCollider`] cast.
+#[derive(Clone, Copy, Debug)]
+pub struct Prank3dHit {
+    /// The entity that was hit.
+    pub entity: Entity,
+
+    /// Distance along the ray that the hit occurred at.
+    pub distance: f32,
+
+    /// Normal of the surface that was hit.
+    pub normal: Vec3,
+}
+
+/// The [`Prank3dCollider`] that is currently in use.
+///
+/// Defaults to [`Prank3dAabbCollider`].
+#[derive(Resource)]
+pub struct Prank3dColliderBackend(Box<dyn Prank3dCollider>);
+
+impl Prank3dColliderBackend {
+    /// Creates a new backend from `collider`.
+    pub fn new(collider: impl Prank3dCollider) -> Self {
+        Self(Box::new(collider))
+    }
+}
+
+impl Deref for Prank3dColliderBackend {
+    type Target = dyn Prank3dCollider;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl Default for Prank3dColliderBackend {
+    fn default() -> Self {
+        Self::new(Prank3dAabbCollider)
+    }
+}
+
+/// Built-in [`Prank3dCollider`] that casts against the [`Aabb`] of every entity.
+///
+/// Only entities whose bounding sphere is within reach of the cast are tested exactly.
+///
+/// Rays are tested against the exact [`Aabb`], whereas spheres are tested against the [`Aabb`]
+/// inflated by their radius, which makes its edges and corners slightly boxier than they would be
+/// in an exact sweep.
+///
+/// Rays that start inside an [`Aabb`] are tested against the triangles of its [`Mesh`] instead, so
+/// that the ground is found inside terrain, rooms and merged level meshes.
+#[derive(Default)]
+pub struct Prank3dAabbCollider;
+
+impl Prank3dCollider for Prank3dAabbCollider {
+    fn cast_sphere(
+        &self,
+        world: &World,
+        ray: Ray,
+        max_distance: f32,
+        radius: f32,
+        exclude: Entity,
+    ) -> Option<Prank3dHit> {
+        self.cast(world, ray, max_distance, radius, exclude)
+    }
+
+    fn cast_ray(
+        &self,
+        world: &World,
+        ray: Ray,
+        max_distance: f32,
+        exclude: Entity,
+    ) -> Option<Prank3dHit> {
+        self.cast(world, ray, max_distance, 0.0, exclude)
+    }
+}
+
+impl Prank3dAabbCollider {
+    /// Sweeps a sphere of `radius` along `ray` against every entity, and against mesh triangles of
+    /// the ones that a ray starts inside of.
+    fn cast(
+        &self,
+        world: &World,
+        ray: Ray,
+        max_distance: f32,
+        radius: f32,
+        exclude: Entity,
+    ) -> Option<Prank3dHit> {
+        let aabb_id = world.components().component_id::<Aabb>()?;
+        let meshes = world.get_resource::<Assets<Mesh>>();
+
+        world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(aabb_id))
+            .flat_map(|archetype| archetype.entities())
+            .map(|entity| world.entity(entity.entity()))
+            .filter(|entity| entity.id() != exclude)
+            .filter_map(|entity| {
+                let aabb = entity.get::<Aabb>()?;
+                let transform = entity.get::<GlobalTransform>()?;
+                if !near_segment(ray, max_distance, aabb, transform, radius) {
+                    return None;
+                }
+
+                let (distance, normal) = match cast_obb(ray, aabb, transform, radius) {
+                    Some(hit) => hit,
+                    None if radius <= 0.0 && contains_obb(ray.origin, aabb, transform) => {
+                        let mesh = entity
+                            .get::<Handle<Mesh>>()
+                            .and_then(|handle| meshes?.get(handle))?;
+                        cast_mesh(ray, mesh, transform)?
+                    }
+                    None => return None,
+                };
+
+                Some(Prank3dHit {
+                    entity: entity.id(),
+                    distance,
+                    normal,
+                })
+            })
+            .filter(|hit| hit.distance <= max_distance)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+/// Casts `ray` against `aabb` oriented by `transform` and inflated by `radius`.
+///
+/// Returns distance and world space normal of the entering face, or `None` if `ray` starts inside
+/// the box so that the [`Camera`] is always able to get out.
+pub(super) fn cast_obb(
+    ray: Ray,
+    aabb: &Aabb,
+    transform: &GlobalTransform,
+    radius: f32,
+) -> Option<(f32, Vec3)> {
+    let inverse = transform.affine().inverse();
+    let origin = inverse.transform_point3(ray.origin);
+    let direction = inverse.transform_vector3(ray.direction);
+
+    let (scale, _, _) = transform.to_scale_rotation_translation();
+    let half_extents = Vec3::from(aabb.half_extents) + radius / scale.abs();
+    let min = Vec3::from(aabb.center) - half_extents;
+    let max = Vec3::from(aabb.center) + half_extents;
+
+    let mut enter = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut normal = Vec3::ZERO;
+    for axis in 0..3 {
+        if direction[axis].abs() <= f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let near = (min[axis] - origin[axis]) / direction[axis];
+        let far = (max[axis] - origin[axis]) / direction[axis];
+        let (near, far, sign) = if near <= far {
+            (near, far, -1.0)
+        } else {
+            (far, near, 1.0)
+        };
+
+        if near > enter {
+            enter = near;
+            normal = Vec3::ZERO;
+            normal[axis] = sign;
+        }
+        exit = exit.min(far);
+    }
+
+    if enter < 0.0 || enter > exit {
+        return None;
+    }
+
+    let normal = inverse.matrix3.transpose().mul_vec3(normal).normalize();
+    Some((enter, normal))
+}
+
+/// Whether the bounding sphere of `aabb` oriented by `transform` and inflated by `radius` comes
+/// within reach of `ray` up to `max_distance`.
+fn near_segment(
+    ray: Ray,
+    max_distance: f32,
+    aabb: &Aabb,
+    transform: &GlobalTransform,
+    radius: f32,
+) -> bool {
+    let center = transform.transform_point(Vec3::from(aabb.center));
+    let (scale, _, _) = transform.to_scale_rotation_translation();
+    let bound = (Vec3::from(aabb.half_extents) * scale.abs()).length() + radius;
+
+    let along = (center - ray.origin)
+        .dot(ray.direction)
+        .clamp(0.0, max_distance);
+    ray.get_point(along).distance_squared(center) <= bound * bound
+}
+
+/// Whether `point` is inside `aabb` oriented by `transform`.
+fn contains_obb(point: Vec3, aabb: &Aabb, transform: &GlobalTransform) -> bool {
+    let point = transform.affine().inverse().transform_point3(point);
+    let offset = (point - Vec3::from(aabb.center)).abs();
+
+    offset.cmple(Vec3::from(aabb.half_extents)).all()
+}
+
+/// Moves from `start` towards `end` while sliding along every surface hit on the way.
+fn slide(
+    collider: &dyn Prank3dCollider,
+    world: &World,
+    start: Vec3,
+    end: Vec3,
+    radius: f32,
+    exclude: Entity,
+) -> Vec3 {
+    let mut position = start;
+    let mut remaining = end - start;
+
+    for _ in 0..MAX_SLIDES {
+        let distance = remaining.length();
+        if distance <= f32::EPSILON {
+            break;
+        }
+
+        let ray = Ray {
+            origin: position,
+            direction: remaining / distance,
+        };
+        let Some(hit) = collider.cast_sphere(world, ray, distance, radius, exclude) else {
+            position += remaining;
+            break;
+        };
+
+        let travel = (hit.distance - SKIN_WIDTH).max(0.0);
+        position += travel * ray.direction;
+        remaining = (distance - travel) * ray.direction;
+        remaining -= remaining.dot(hit.normal) * hit.normal;
+    }
+
+    position
+}
+
+pub(super) fn collide(world: &mut World) {
+    let entity = world.resource::<Prank3dActive>().expect("is active");
+    let (start, end, radius) = {
+        let entity_ref = world.entity(entity);
+        let prank = entity_ref.get::<Prank3d>().expect("exists");
+        if !prank.collision {
+            return;
+        }
+        let transform = entity_ref.get::<Transform>().expect("exists");
+
+        (
+            transform.translation,
+            prank.translation,
+            prank.collision_radius,
+        )
+    };
+
+    let backend = world.resource::<Prank3dColliderBackend>();
+    let translation = slide(&**backend, world, start, end, radius, entity);
+
+    world
+        .get_mut::<Prank3d>(entity)
+        .expect("exists")
+        .translation = translation;
+}
+
+fn toggle(
+    active: Res<Prank3dActive>,
+    mut pranks: Query<&mut Prank3d>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let mut prank = pranks.get_mut(active.expect("is active")).expect("exists");
+
+    if keyboard.just_pressed(prank.collision_key) {
+        prank.collision = !prank.collision;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::mesh::shape;
+
+    fn unit_box() -> Aabb {
+        Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0))
+    }
+
+    fn ray(origin: Vec3, direction: Vec3) -> Ray {
+        Ray { origin, direction }
+    }
+
+    fn assert_hit(hit: Option<(f32, Vec3)>, distance: f32, normal: Vec3) {
+        let (hit_distance, hit_normal) = hit.expect("hits");
+        assert!(
+            (hit_distance - distance).abs() < 1e-5,
+            "distance {} instead of {}",
+            hit_distance,
+            distance,
+        );
+        assert!(
+            hit_normal.abs_diff_eq(normal, 1e-5),
+            "normal {} instead of {}",
+            hit_normal,
+            normal,
+        );
+    }
+
+    #[test]
+    fn obb_hit_from_each_axis() {
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            for direction in [axis, -axis] {
+                let hit = cast_obb(
+                    ray(-5.0 * direction, direction),
+                    &unit_box(),
+                    &GlobalTransform::IDENTITY,
+                    0.0,
+                );
+                assert_hit(hit, 4.0, -direction);
+            }
+        }
+    }
+
+    #[test]
+    fn obb_hit_off_center() {
+        let hit = cast_obb(
+            ray(Vec3::new(0.5, -0.5, -5.0), Vec3::Z),
+            &unit_box(),
+            &GlobalTransform::IDENTITY,
+            0.0,
+        );
+        assert_hit(hit, 4.0, Vec3::NEG_Z);
+    }
+
+    #[test]
+    fn obb_inflated_by_radius() {
+        let hit = cast_obb(
+            ray(Vec3::new(-5.0, 0.0, 0.0), Vec3::X),
+            &unit_box(),
+            &GlobalTransform::IDENTITY,
+            0.5,
+        );
+        assert_hit(hit, 3.5, Vec3::NEG_X);
+    }
+
+    #[test]
+    fn obb_ray_inside_misses() {
+        for direction in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, 1.0, 1.0).normalize()] {
+            let hit = cast_obb(
+                ray(Vec3::new(0.2, -0.3, 0.1), direction),
+                &unit_box(),
+                &GlobalTransform::IDENTITY,
+                0.0,
+            );
+            assert!(hit.is_none());
+        }
+    }
+
+    #[test]
+    fn obb_ray_pointing_away_misses() {
+        let hit = cast_obb(
+            ray(Vec3::new(-5.0, 0.0, 0.0), Vec3::NEG_X),
+            &unit_box(),
+            &GlobalTransform::IDENTITY,
+            0.0,
+        );
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn obb_parallel_ray_outside_slab_misses() {
+        for origin in [Vec3::new(-5.0, 2.0, 0.0), Vec3::new(-5.0, 0.0, -1.5)] {
+            let hit = cast_obb(
+                ray(origin, Vec3::X),
+                &unit_box(),
+                &GlobalTransform::IDENTITY,
+                0.0,
+            );
+            assert!(hit.is_none());
+        }
+    }
+
+    #[test]
+    fn obb_parallel_ray_inside_slab_hits() {
+        let hit = cast_obb(
+            ray(Vec3::new(-5.0, 0.9, -0.9), Vec3::X),
+            &unit_box(),
+            &GlobalTransform::IDENTITY,
+            0.0,
+        );
+        assert_hit(hit, 4.0, Vec3::NEG_X);
+    }
+
+    #[test]
+    fn obb_scaled() {
+        let transform = GlobalTransform::from(Transform::from_scale(Vec3::new(2.0, 1.0, 1.0)));
+
+        let hit = cast_obb(
+            ray(Vec3::new(-5.0, 0.0, 0.0), Vec3::X),
+            &unit_box(),
+            &transform,
+            0.0,
+        );
+        assert_hit(hit, 3.0, Vec3::NEG_X);
+
+        let hit = cast_obb(
+            ray(Vec3::new(-5.0, 0.0, 0.0), Vec3::X),
+            &unit_box(),
+            &transform,
+            0.5,
+        );
+        assert_hit(hit, 2.5, Vec3::NEG_X);
+
+        let hit = cast_obb(
+            ray(Vec3::new(0.0, -5.0, 0.0), Vec3::Y),
+            &unit_box(),
+            &transform,
+            0.5,
+        );
+        assert_hit(hit, 3.5, Vec3::NEG_Y);
+    }
+
+    #[test]
+    fn obb_rotated_and_translated() {
+        let aabb = Aabb::from_min_max(Vec3::new(-2.0, -1.0, -1.0), Vec3::new(2.0, 1.0, 1.0));
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(10.0, 0.0, 0.0)
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+        );
+
+        let hit = cast_obb(
+            ray(Vec3::new(10.0, 0.0, -5.0), Vec3::Z),
+            &aabb,
+            &transform,
+            0.0,
+        );
+        assert_hit(hit, 3.0, Vec3::NEG_Z);
+
+        let hit = cast_obb(
+            ray(Vec3::new(5.0, 0.0, 0.0), Vec3::X),
+            &aabb,
+            &transform,
+            0.0,
+        );
+        assert_hit(hit, 4.0, Vec3::NEG_X);
+
+        let hit = cast_obb(
+            ray(Vec3::new(5.0, 0.0, 1.5), Vec3::X),
+            &aabb,
+            &transform,
+            0.0,
+        );
+        assert_hit(hit, 4.0, Vec3::NEG_X);
+
+        let hit = cast_obb(
+            ray(Vec3::new(5.0, 0.0, 2.5), Vec3::X),
+            &aabb,
+            &transform,
+            0.0,
+        );
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn ray_inside_aabb_hits_mesh() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_asset::<Mesh>();
+
+        let mesh = Mesh::from(shape::Box::new(10.0, 4.0, 10.0));
+        let aabb = mesh.compute_aabb().expect("has positions");
+        let handle = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+        let room = app
+            .world
+            .spawn((
+                handle,
+                aabb,
+                GlobalTransform::from(Transform::from_xyz(0.0, 2.0, 0.0)),
+            ))
+            .id();
+
+        let ray = Ray {
+            origin: Vec3::new(1.0, 1.7, -1.0),
+            direction: Vec3::NEG_Y,
+        };
+        let hit = Prank3dAabbCollider
+            .cast_ray(&app.world, ray, f32::INFINITY, Entity::PLACEHOLDER)
+            .expect("hits the floor");
+
+        assert_eq!(hit.entity, room);
+        assert!((hit.distance - 1.7).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn sphere_inside_aabb_misses() {
+        let mut world = World::new();
+        world.spawn((
+            Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0)),
+            GlobalTransform::IDENTITY,
+        ));
+
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::NEG_Y,
+        };
+        assert!(Prank3dAabbCollider
+            .cast_sphere(&world, ray, f32::INFINITY, 0.25, Entity::PLACEHOLDER)
+            .is_none());
+    }
+
+    #[test]
+    fn near_segment_rejects_out_of_reach() {
+        let aabb = unit_box();
+        let transform = GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 0.0));
+        let ray = ray(Vec3::ZERO, Vec3::X);
+
+        assert!(near_segment(ray, 9.5, &aabb, &transform, 0.0));
+        assert!(!near_segment(ray, 5.0, &aabb, &transform, 0.0));
+        assert!(!near_segment(ray, 5.0, &aabb, &transform, 2.0));
+        assert!(near_segment(ray, 5.0, &aabb, &transform, 5.0));
+    }
+
+    #[test]
+    fn cast_skips_entities_without_aabb() {
+        let mut world = World::new();
+        world.spawn(GlobalTransform::IDENTITY);
+        let wall = world
+            .spawn((unit_box(), GlobalTransform::from_xyz(0.0, 0.0, -5.0)))
+            .id();
+
+        let hit = Prank3dAabbCollider
+            .cast_ray(
+                &world,
+                ray(Vec3::ZERO, Vec3::NEG_Z),
+                10.0,
+                Entity::PLACEHOLDER,
+            )
+            .expect("hits the wall");
+        assert_eq!(hit.entity, wall);
+        assert!((hit.distance - 4.0).abs() < 1e-5);
+    }
+}
@@ -1,73 +1,373 @@
 //! Provides three-dimensional hint gizmo.
 
-use super::{any_active, Prank3d, Prank3dActive};
+use super::{
+    any_active, interpolation, pick::Picked, screenshot::overlays_shown, Prank3d, Prank3dActive,
+};
 use crate::PrankConfig;
-use bevy::{ecs::query::Has, prelude::*};
+use bevy::{
+    ecs::query::Has,
+    pbr::CascadeShadowConfig,
+    prelude::*,
+    render::{
+        primitives::Aabb,
+        view::{ComputedVisibility, NoFrustumCulling},
+    },
+};
+
+/// Number of segments that each grid line fades out across.
+const GRID_LINE_SEGMENTS: usize = 8;
+
+/// Distance from the near plane of the active [`Camera`] that the orientation axes are drawn at.
+const AXES_DEPTH: f32 = 1.0;
+
+/// Distance of the orientation axes from the top-left corner of the viewport in logical pixels.
+const AXES_MARGIN: f32 = 10.0;
 
 pub(super) struct Prank3dGizmoPlugin;
 
 impl Plugin for Prank3dGizmoPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (camera, point_light, spot_light).run_if(any_active));
+        app.register_type::<Prank3dGizmoColor>()
+            .register_type::<Prank3dGizmoHidden>()
+            .register_type::<Prank3dGridPlane>()
+            .init_resource::<AabbToggled>()
+            .add_systems(
+                Update,
+                (
+                    camera,
+                    point_light,
+                    spot_light,
+                    directional_light,
+                    toggle_aabb,
+                    aabb.after(toggle_aabb),
+                    picked,
+                    grid,
+                    axes.after(interpolation),
+                )
+                    .run_if(any_active.and_then(overlays_shown)),
+            );
     }
 }
 
 /// Three-dimensional hint gizmo configuration.
 #[derive(Clone, Reflect)]
 pub struct Prank3dGizmoConfig {
+    /// Whether to draw [`Camera`] gizmo.
+    pub camera_enabled: bool,
+
     /// [`Camera`] spherical gizmo radius.
     pub camera_radius: f32,
 
     /// [`Camera`] gizmo color.
     pub camera_color: Color,
 
+    /// [`Camera`] frustum gizmo far plane distance limit.
+    pub camera_frustum_length: f32,
+
+    /// Whether to only draw the frustum of the [`Camera`] closest to the center of view.
+    pub camera_frustum_inspected_only: bool,
+
     /// [`Prank3d`]  gizmo color.
     pub prank_color: Color,
 
+    /// Whether to draw [`PointLight`] gizmo.
+    pub point_light_enabled: bool,
+
     /// [`PointLight`] gizmo radius.
     pub point_light_radius: f32,
 
     /// [`PointLight`] gizmo color.
     pub point_light_color: Color,
 
+    /// Whether to draw [`PointLight`] `range` gizmo.
+    pub point_light_range: bool,
+
+    /// Whether to draw [`SpotLight`] gizmo.
+    pub spot_light_enabled: bool,
+
     /// [`SpotLight`] gizmo radius.
     pub spot_light_radius: f32,
 
     /// [`SpotLight`] gizmo color.
     pub spot_light_color: Color,
+
+    /// Whether to draw [`SpotLight`] `range`, `inner_angle` and `outer_angle` cone gizmos.
+    pub spot_light_cones: bool,
+
+    /// Intensity that light range and cone gizmos become fully opaque at.
+    ///
+    /// Their color is derived from the color of the light.
+    pub light_intensity_reference: f32,
+
+    /// Whether to draw [`DirectionalLight`] gizmo.
+    pub directional_light_enabled: bool,
+
+    /// [`DirectionalLight`] gizmo arrow length.
+    pub directional_light_length: f32,
+
+    /// [`DirectionalLight`] gizmo color, tinted by the color of the light.
+    pub directional_light_color: Color,
+
+    /// Whether to draw [`CascadeShadowConfig`] bounds of [`DirectionalLight`] in the frustum of
+    /// every [`Camera`] but the active one.
+    pub directional_light_cascades: bool,
+
+    /// Whether to draw [`Aabb`] gizmo of every entity.
+    pub aabb_enabled: bool,
+
+    /// Key to toggle [`Aabb`] gizmo with at runtime, starting from `aabb_enabled`.
+    pub aabb_key: KeyCode,
+
+    /// [`Aabb`] gizmo color of visible entities.
+    pub aabb_color: Color,
+
+    /// [`Aabb`] gizmo color of entities hidden by their [`Visibility`] or that of their ancestors.
+    pub aabb_hidden_color: Color,
+
+    /// [`Aabb`] gizmo color of entities culled by every view.
+    pub aabb_culled_color: Color,
+
+    /// [`Aabb`] gizmo color of entities with [`NoFrustumCulling`].
+    pub aabb_no_frustum_culling_color: Color,
+
+    /// Outline gizmo color of the picked entity.
+    pub picked_color: Color,
+
+    /// Whether to draw the world grid gizmo, with the world axes through its origin.
+    pub grid_enabled: bool,
+
+    /// Plane that the world grid lies on.
+    pub grid_plane: Prank3dGridPlane,
+
+    /// Distance between major lines of the world grid.
+    pub grid_spacing: f32,
+
+    /// Number of cells that each major cell of the world grid is divided into.
+    pub grid_subdivisions: u32,
+
+    /// World grid gizmo color.
+    pub grid_color: Color,
+
+    /// Minimum distance from the active [`Camera`] that the world grid fades out at.
+    pub grid_fade_distance: f32,
+
+    /// Scalar of the height of the active [`Camera`] above the world grid that it fades out at,
+    /// if larger than `grid_fade_distance`.
+    pub grid_fade_height_scalar: f32,
+
+    /// Whether to draw the world orientation axes in the corner of the active view.
+    pub axes_enabled: bool,
+
+    /// Length of the world orientation axes in logical pixels.
+    pub axes_size: f32,
 }
 
 impl Default for Prank3dGizmoConfig {
     fn default() -> Self {
         Self {
+            camera_enabled: true,
             camera_radius: 1.0,
             camera_color: Color::CYAN,
+            camera_frustum_length: 5.0,
+            camera_frustum_inspected_only: false,
             prank_color: Color::PINK,
+            point_light_enabled: true,
             point_light_radius: 0.25,
             point_light_color: Color::WHITE,
+            point_light_range: false,
+            spot_light_enabled: true,
             spot_light_radius: 0.25,
             spot_light_color: Color::WHITE,
+            spot_light_cones: false,
+            light_intensity_reference: 800.0,
+            directional_light_enabled: true,
+            directional_light_length: 1.0,
+            directional_light_color: Color::WHITE,
+            directional_light_cascades: false,
+            aabb_enabled: false,
+            aabb_key: KeyCode::B,
+            aabb_color: Color::GREEN,
+            aabb_hidden_color: Color::GRAY,
+            aabb_culled_color: Color::RED,
+            aabb_no_frustum_culling_color: Color::YELLOW,
+            picked_color: Color::ORANGE,
+            grid_enabled: false,
+            grid_plane: Prank3dGridPlane::XZ,
+            grid_spacing: 10.0,
+            grid_subdivisions: 10,
+            grid_color: Color::GRAY,
+            grid_fade_distance: 50.0,
+            grid_fade_height_scalar: 10.0,
+            axes_enabled: false,
+            axes_size: 30.0,
+        }
+    }
+}
+
+/// Plane that the world grid gizmo lies on.
+#[derive(Clone, Copy, Reflect)]
+pub enum Prank3dGridPlane {
+    /// Plane spanned by the `x` and `y` axes.
+    XY,
+
+    /// Plane spanned by the `x` and `z` axes.
+    XZ,
+
+    /// Plane spanned by the `y` and `z` axes.
+    YZ,
+}
+
+impl Prank3dGridPlane {
+    /// Returns the two axes that span the plane, followed by its normal.
+    fn axes(self) -> (Vec3, Vec3, Vec3) {
+        match self {
+            Self::XY => (Vec3::X, Vec3::Y, Vec3::Z),
+            Self::XZ => (Vec3::X, Vec3::Z, Vec3::Y),
+            Self::YZ => (Vec3::Y, Vec3::Z, Vec3::X),
+        }
+    }
+}
+
+/// Excludes an entity from every [`Prank3d`] gizmo.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((Prank3dGizmoHidden, PointLightBundle::default()));
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Default, Reflect, Component)]
+#[reflect(Component)]
+pub struct Prank3dGizmoHidden;
+
+/// Adds custom gizmos that are drawn only while a [`Prank3d`] is active.
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// #[derive(Component)]
+/// struct SpawnPoint;
+///
+/// fn spawn_point(gizmos: &mut Gizmos, transform: &GlobalTransform, _: &SpawnPoint) {
+///     gizmos.ray(transform.translation(), Vec3::Y, Color::GREEN);
+/// }
+///
+/// App::new()
+///     .add_plugins((DefaultPlugins, PrankPlugin::default()))
+///     .add_prank_gizmo::<SpawnPoint>(spawn_point)
+///     .run();
+/// ```
+pub trait Prank3dGizmoAppExt {
+    /// Draws every entity with `C` component, but without [`Prank3dGizmoHidden`], using `draw`.
+    fn add_prank_gizmo<C: Component>(
+        &mut self,
+        draw: fn(&mut Gizmos, &GlobalTransform, &C),
+    ) -> &mut Self;
+}
+
+impl Prank3dGizmoAppExt for App {
+    fn add_prank_gizmo<C: Component>(
+        &mut self,
+        draw: fn(&mut Gizmos, &GlobalTransform, &C),
+    ) -> &mut Self {
+        if let Some(mut custom) = self.world.get_resource_mut::<CustomGizmos<C>>() {
+            custom.0.push(draw);
+            return self;
         }
+
+        self.insert_resource(CustomGizmos(vec![draw])).add_systems(
+            Update,
+            custom::<C>.run_if(any_active.and_then(overlays_shown)),
+        )
     }
 }
 
+/// Whether [`Aabb`] gizmo has been toggled at runtime, relative to `aabb_enabled` field of
+/// [`Prank3dGizmoConfig`].
+#[derive(Default, Resource)]
+struct AabbToggled(bool);
+
+/// Draw functions of every custom gizmo of `C` component.
+#[derive(Resource)]
+struct CustomGizmos<C: Component>(Vec<fn(&mut Gizmos, &GlobalTransform, &C)>);
+
+/// Overrides gizmo color of a [`Camera`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Prank3dGizmoColor(Color::ORANGE),
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Clone, Copy, Default, Reflect, Component)]
+#[reflect(Component)]
+pub struct Prank3dGizmoColor(pub Color);
+
 fn camera(
     mut gizmos: Gizmos,
     config: Res<PrankConfig>,
     active: Res<Prank3dActive>,
-    cameras: Query<(Entity, &GlobalTransform, Has<Prank3d>), With<Camera>>,
+    pranks: Query<&GlobalTransform, With<Prank3d>>,
+    cameras: Query<
+        (
+            Entity,
+            &Camera,
+            Option<&Projection>,
+            &GlobalTransform,
+            Option<&Prank3dGizmoColor>,
+            Has<Prank3d>,
+        ),
+        Without<Prank3dGizmoHidden>,
+    >,
 ) {
-    let Some(config) = config.gizmo3d.clone() else {
+    let Some(config) = config
+        .gizmo3d
+        .clone()
+        .filter(|config| config.camera_enabled)
+    else {
         return;
     };
     let prank_entity = active.expect("is active");
+    let prank_transform = pranks.get(prank_entity).expect("exists");
+    let inspected = cameras
+        .iter()
+        .filter(|(camera_entity, ..)| *camera_entity != prank_entity)
+        .max_by(|(_, _, _, a, _, _), (_, _, _, b, _, _)| {
+            let forward = prank_transform.forward();
+            let a = (a.translation() - prank_transform.translation()).normalize();
+            let b = (b.translation() - prank_transform.translation()).normalize();
+            a.dot(forward).total_cmp(&b.dot(forward))
+        })
+        .map(|(camera_entity, ..)| camera_entity);
 
-    for (camera_entity, camera_transform, camera_has_prank) in cameras.iter() {
+    for (camera_entity, camera, projection, camera_transform, camera_color, camera_has_prank) in
+        cameras.iter()
+    {
         if camera_entity == prank_entity {
             continue;
         }
 
-        let color = if camera_has_prank {
+        let color = if let Some(camera_color) = camera_color {
+            camera_color.0
+        } else if camera_has_prank {
             config.prank_color
         } else {
             config.camera_color
@@ -75,24 +375,82 @@ fn camera(
 
         let (_, rotation, translation) = camera_transform.to_scale_rotation_translation();
         gizmos.sphere(translation, rotation, config.camera_radius, color);
-        gizmos.ray(
-            translation,
-            config.camera_radius * camera_transform.forward(),
-            color,
-        );
+
+        let projection = projection
+            .filter(|_| !config.camera_frustum_inspected_only || inspected == Some(camera_entity));
+        let Some(projection) = projection else {
+            gizmos.ray(
+                translation,
+                config.camera_radius * camera_transform.forward(),
+                color,
+            );
+            continue;
+        };
+
+        let (near, far) = frustum_range(projection, config.camera_frustum_length);
+        let [near, far] = [near, far].map(|depth| {
+            frustum_plane(camera, projection, depth)
+                .map(|corner| camera_transform.transform_point(corner))
+        });
+        gizmos.linestrip([near[0], near[1], near[2], near[3], near[0]], color);
+        gizmos.linestrip([far[0], far[1], far[2], far[3], far[0]], color);
+        for (near, far) in near.into_iter().zip(far) {
+            gizmos.line(near, far, color);
+        }
     }
 }
 
+/// Returns near and far plane depths of `projection`, where the far plane is clipped to `length`.
+fn frustum_range(projection: &Projection, length: f32) -> (f32, f32) {
+    let (near, far) = match projection {
+        Projection::Perspective(projection) => (projection.near, projection.far),
+        Projection::Orthographic(projection) => (projection.near, projection.far),
+    };
+
+    (near, far.min(length).max(near))
+}
+
+/// Returns corners of the plane at `depth` in the frustum of `projection`, in local space of its
+/// [`Camera`].
+fn frustum_plane(camera: &Camera, projection: &Projection, depth: f32) -> [Vec3; 4] {
+    let Rect { min, max } = match projection {
+        Projection::Perspective(projection) => {
+            let aspect_ratio = camera
+                .logical_viewport_size()
+                .map(|size| size.x / size.y)
+                .unwrap_or(projection.aspect_ratio);
+            let half_height = (0.5 * projection.fov).tan() * depth;
+
+            Rect::from_center_half_size(
+                Vec2::ZERO,
+                Vec2::new(aspect_ratio * half_height, half_height),
+            )
+        }
+        Projection::Orthographic(projection) => projection.area,
+    };
+
+    [
+        Vec3::new(min.x, min.y, -depth),
+        Vec3::new(max.x, min.y, -depth),
+        Vec3::new(max.x, max.y, -depth),
+        Vec3::new(min.x, max.y, -depth),
+    ]
+}
+
 fn point_light(
     mut gizmos: Gizmos,
     config: Res<PrankConfig>,
-    point_lights: Query<&GlobalTransform, With<PointLight>>,
+    point_lights: Query<(&GlobalTransform, &PointLight), Without<Prank3dGizmoHidden>>,
 ) {
-    let Some(config) = config.gizmo3d.clone() else {
+    let Some(config) = config
+        .gizmo3d
+        .clone()
+        .filter(|config| config.point_light_enabled)
+    else {
         return;
     };
 
-    for transform in point_lights.iter() {
+    for (transform, light) in point_lights.iter() {
         let (_, rotation, translation) = transform.to_scale_rotation_translation();
         gizmos.sphere(
             translation,
@@ -100,19 +458,30 @@ fn point_light(
             config.point_light_radius,
             config.point_light_color,
         );
+
+        if config.point_light_range {
+            let color = light_color(light.color, light.intensity, &config);
+            gizmos
+                .sphere(translation, rotation, light.range, color)
+                .circle_segments(64);
+        }
     }
 }
 
 fn spot_light(
     mut gizmos: Gizmos,
     config: Res<PrankConfig>,
-    spot_lights: Query<&GlobalTransform, With<SpotLight>>,
+    spot_lights: Query<(&GlobalTransform, &SpotLight), Without<Prank3dGizmoHidden>>,
 ) {
-    let Some(config) = config.gizmo3d.clone() else {
+    let Some(config) = config
+        .gizmo3d
+        .clone()
+        .filter(|config| config.spot_light_enabled)
+    else {
         return;
     };
 
-    for transform in spot_lights.iter() {
+    for (transform, light) in spot_lights.iter() {
         let (_, rotation, translation) = transform.to_scale_rotation_translation();
         gizmos.sphere(
             translation,
@@ -125,5 +494,282 @@ fn spot_light(
             config.spot_light_radius * transform.forward(),
             config.spot_light_color,
         );
+
+        if config.spot_light_cones {
+            let color = light_color(light.color, light.intensity, &config);
+            cone(
+                &mut gizmos,
+                transform,
+                light.range,
+                light.outer_angle,
+                color,
+            );
+            cone(
+                &mut gizmos,
+                transform,
+                light.range,
+                light.inner_angle,
+                color.with_a(0.5 * color.a()),
+            );
+        }
+    }
+}
+
+/// Returns `color` of a light whose opacity is proportional to its `intensity`.
+fn light_color(color: Color, intensity: f32, config: &Prank3dGizmoConfig) -> Color {
+    color.with_a((intensity / config.light_intensity_reference).clamp(0.1, 1.0))
+}
+
+/// Draws a cone of `range` length and `angle` half angle from `transform` along its forward axis.
+fn cone(gizmos: &mut Gizmos, transform: &GlobalTransform, range: f32, angle: f32, color: Color) {
+    let apex = transform.translation();
+    let (forward, right, up) = (transform.forward(), transform.right(), transform.up());
+    let center = apex + range * angle.cos() * forward;
+    let radius = range * angle.sin();
+
+    gizmos.circle(center, forward, radius, color);
+    for side in [right, -right, up, -up] {
+        gizmos.line(apex, center + radius * side, color);
+    }
+}
+
+fn directional_light(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    directional_lights: Query<
+        (
+            &GlobalTransform,
+            &DirectionalLight,
+            Option<&CascadeShadowConfig>,
+        ),
+        Without<Prank3dGizmoHidden>,
+    >,
+    cameras: Query<(Entity, &Camera, &Projection, &GlobalTransform), Without<Prank3dGizmoHidden>>,
+) {
+    let Some(config) = config
+        .gizmo3d
+        .clone()
+        .filter(|config| config.directional_light_enabled)
+    else {
+        return;
+    };
+    let prank_entity = active.expect("is active");
+
+    for (transform, light, cascade_shadow_config) in directional_lights.iter() {
+        let color = config.directional_light_color * light.color.as_rgba_f32();
+        let length = config.directional_light_length;
+        let translation = transform.translation();
+        let (right, up) = (transform.right(), transform.up());
+
+        for offset in [Vec3::ZERO, right, -right, up, -up] {
+            arrow(
+                &mut gizmos,
+                translation + 0.5 * length * offset,
+                length * transform.forward(),
+                right,
+                color,
+            );
+        }
+
+        let Some(cascade_shadow_config) = cascade_shadow_config
+            .filter(|_| config.directional_light_cascades && light.shadows_enabled)
+        else {
+            continue;
+        };
+
+        for (camera_entity, camera, projection, camera_transform) in cameras.iter() {
+            if camera_entity == prank_entity {
+                continue;
+            }
+
+            for &bound in cascade_shadow_config.bounds.iter() {
+                let corners = frustum_plane(camera, projection, bound)
+                    .map(|corner| camera_transform.transform_point(corner));
+                gizmos.linestrip(
+                    [corners[0], corners[1], corners[2], corners[3], corners[0]],
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// Draws an arrow from `start` along `vector` with its head spread along `side`.
+fn arrow(gizmos: &mut Gizmos, start: Vec3, vector: Vec3, side: Vec3, color: Color) {
+    let end = start + vector;
+    let back = end - 0.25 * vector;
+    let spread = 0.125 * vector.length() * side;
+
+    gizmos.line(start, end, color);
+    gizmos.line(end, back + spread, color);
+    gizmos.line(end, back - spread, color);
+}
+
+fn toggle_aabb(
+    mut toggled: ResMut<AabbToggled>,
+    config: Res<PrankConfig>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let Some(config) = config.gizmo3d.as_ref() else {
+        return;
+    };
+
+    if keyboard.just_pressed(config.aabb_key) {
+        toggled.0 = !toggled.0;
+    }
+}
+
+fn aabb(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    toggled: Res<AabbToggled>,
+    entities: Query<
+        (
+            &GlobalTransform,
+            &Aabb,
+            &ComputedVisibility,
+            Has<NoFrustumCulling>,
+        ),
+        Without<Prank3dGizmoHidden>,
+    >,
+) {
+    let Some(config) = config
+        .gizmo3d
+        .clone()
+        .filter(|config| config.aabb_enabled != toggled.0)
+    else {
+        return;
+    };
+
+    for (transform, aabb, visibility, no_frustum_culling) in entities.iter() {
+        let color = if !visibility.is_visible_in_hierarchy() {
+            config.aabb_hidden_color
+        } else if no_frustum_culling {
+            config.aabb_no_frustum_culling_color
+        } else if !visibility.is_visible_in_view() {
+            config.aabb_culled_color
+        } else {
+            config.aabb_color
+        };
+
+        let local = Transform::from_translation(aabb.center.into())
+            .with_scale(Vec3::from(aabb.half_extents) * 2.0);
+        gizmos.cuboid(*transform * local, color);
+    }
+}
+
+fn picked(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    picked: Res<Picked>,
+    entities: Query<(&GlobalTransform, &Aabb)>,
+) {
+    let Some(config) = config.gizmo3d.clone() else {
+        return;
+    };
+    let Some((transform, aabb)) = picked.and_then(|entity| entities.get(entity).ok()) else {
+        return;
+    };
+
+    let local = Transform::from_translation(aabb.center.into())
+        .with_scale(Vec3::from(aabb.half_extents) * 2.0 * 1.01);
+    gizmos.cuboid(*transform * local, config.picked_color);
+}
+
+fn grid(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    pranks: Query<&GlobalTransform, With<Prank3d>>,
+) {
+    let Some(config) = config.gizmo3d.clone().filter(|config| config.grid_enabled) else {
+        return;
+    };
+    let transform = pranks.get(active.expect("is active")).expect("exists");
+
+    let (u, v, normal) = config.grid_plane.axes();
+    let height = transform.translation().dot(normal);
+    let center = transform.translation() - height * normal;
+    let radius = config
+        .grid_fade_distance
+        .max(config.grid_fade_height_scalar * height.abs());
+    let subdivisions = config.grid_subdivisions.max(1) as i32;
+    let step = config.grid_spacing / subdivisions as f32;
+    let count = (radius / step).ceil() as i32;
+
+    for (along, across) in [(u, v), (v, u)] {
+        let nearest = (center.dot(across) / step).round() as i32;
+        for i in (nearest - count)..=(nearest + count) {
+            let offset = i as f32 * step - center.dot(across);
+            if offset.abs() >= radius {
+                continue;
+            }
+
+            let color = if i == 0 {
+                Color::rgb(along.x, along.y, along.z)
+            } else if i % subdivisions == 0 {
+                config.grid_color
+            } else {
+                config.grid_color.with_a(0.5 * config.grid_color.a())
+            };
+
+            let half_length = (radius.powi(2) - offset.powi(2)).sqrt();
+            let start = center + offset * across - half_length * along;
+            let points = (0..=GRID_LINE_SEGMENTS).map(|segment| {
+                let point = start
+                    + (2.0 * half_length * segment as f32 / GRID_LINE_SEGMENTS as f32) * along;
+                let fade = 1.0 - point.distance(center) / radius;
+                (point, color.with_a(fade * color.a()))
+            });
+            gizmos.linestrip_gradient(points);
+        }
+    }
+}
+
+fn axes(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    pranks: Query<(&Camera, &Transform), With<Prank3d>>,
+) {
+    let Some(config) = config.gizmo3d.clone().filter(|config| config.axes_enabled) else {
+        return;
+    };
+    let (camera, transform) = pranks.get(active.expect("is active")).expect("exists");
+
+    // `GlobalTransform` is not propagated yet, and would make the axes lag behind by a frame.
+    let transform = GlobalTransform::from(*transform);
+    let center = Vec2::splat(AXES_MARGIN + config.axes_size);
+    let point = |position: Vec2| {
+        camera
+            .viewport_to_world(&transform, position)
+            .map(|ray| ray.get_point(AXES_DEPTH))
+    };
+    let (Some(origin), Some(edge)) = (point(center), point(center + config.axes_size * Vec2::X))
+    else {
+        return;
+    };
+
+    let length = origin.distance(edge);
+    for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+        gizmos.ray(origin, length * axis, Color::rgb(axis.x, axis.y, axis.z));
+    }
+}
+
+fn custom<C: Component>(
+    mut gizmos: Gizmos,
+    config: Res<PrankConfig>,
+    custom: Res<CustomGizmos<C>>,
+    entities: Query<(&GlobalTransform, &C), Without<Prank3dGizmoHidden>>,
+) {
+    if config.gizmo3d.is_none() {
+        return;
+    }
+
+    for (transform, component) in entities.iter() {
+        for draw in custom.0.iter() {
+            draw(&mut gizmos, transform, component);
+        }
     }
 }
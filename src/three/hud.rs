@@ -1,183 +1,1259 @@
 //! Provides three-dimensional camera HUD overlay.
 
-use super::{any_active, Prank3d, Prank3dActive};
+use super::{
+    any_active,
+    gizmo::Prank3dGizmoHidden,
+    pick::{cast_targets, Picked},
+    screenshot::Capturing,
+    Prank3d, Prank3dActive, Prank3dState,
+};
 use crate::PrankConfig;
-use bevy::prelude::*;
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    ecs::system::BoxedSystem,
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+    render::{primitives::Aabb, view::RenderLayers},
+    utils::HashMap,
+};
+use std::collections::VecDeque;
 
 pub(super) struct Prank3dHudPlugin;
 
 impl Plugin for Prank3dHudPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                spawn.run_if(just_activated),
-                despawn.run_if(just_inactivated),
-                (sync_translation, sync_fps, sync_fov, sync_speed).run_if(any_active),
-            ),
-        );
+        app.register_type::<Prank3dHudSegment>()
+            .register_type::<Prank3dHudAnchor>()
+            .register_type::<Prank3dHudLayout>()
+            .init_resource::<Aimed>()
+            .init_resource::<FrameTimes>()
+            .init_resource::<HudPage>()
+            .init_resource::<AppliedConfig>()
+            .init_resource::<HudOpacity>()
+            .init_resource::<HudSegments>()
+            .add_systems(
+                Update,
+                (
+                    sync_config.run_if(resource_changed::<PrankConfig>()),
+                    despawn.after(sync_config),
+                    sync_camera.after(sync_config),
+                    (
+                        cycle_page.before(despawn),
+                        apply_deferred.after(despawn).before(spawn),
+                        spawn.after(despawn),
+                        sync_viewport.after(spawn),
+                        aim.run_if(aiming),
+                        measure_frame_time,
+                        sync_segments
+                            .after(spawn)
+                            .after(aim)
+                            .after(measure_frame_time),
+                        toggle_visibility,
+                        fade.after(toggle_visibility),
+                        sync_fps_color.after(sync_segments).after(fade),
+                        sync_opacity.after(spawn).after(fade),
+                    )
+                        .run_if(any_active),
+                ),
+            );
+
+        register_segment(&mut app.world, Prank3dHudSegment::Translation, translation);
+        register_segment(&mut app.world, Prank3dHudSegment::Rotation, rotation);
+        register_segment(&mut app.world, Prank3dHudSegment::Forward, forward);
+        register_segment(&mut app.world, Prank3dHudSegment::Heading, heading);
+        register_segment(&mut app.world, Prank3dHudSegment::Fps, fps);
+        register_segment(&mut app.world, Prank3dHudSegment::Fov, fov);
+        register_segment(&mut app.world, Prank3dHudSegment::Speed, speed);
+        register_segment(&mut app.world, Prank3dHudSegment::Collision, collision);
+        register_segment(&mut app.world, Prank3dHudSegment::Picked, picked);
+        register_segment(&mut app.world, Prank3dHudSegment::Aimed, aimed);
+        register_segment(&mut app.world, Prank3dHudSegment::Entities, entities);
+        register_segment(&mut app.world, Prank3dHudSegment::Meshes, meshes);
+        register_segment(&mut app.world, Prank3dHudSegment::Lights, lights);
+        register_segment(&mut app.world, Prank3dHudSegment::Cameras, cameras);
+        register_segment(&mut app.world, Prank3dHudSegment::Assets, assets);
     }
 }
 
 /// Three-dimensional camera HUD overlay configuration.
+///
+/// Every active [`Prank3d`] gets its own overlay, laid out within its viewport.
+///
+/// The overlays are drawn by a [`Camera`] of their own that covers the whole window, so UI is
+/// hidden from every [`Prank3d`] with an overlay by inserting [`UiCameraConfig`] until the overlay
+/// is gone.
 #[derive(Clone, Reflect)]
 pub struct Prank3dHudConfig {
-    /// Overlay height.
+    /// Where the overlay is placed within the viewport.
+    pub anchor: Prank3dHudAnchor,
+
+    /// Direction that the segments are laid out in.
+    pub layout: Prank3dHudLayout,
+
+    /// Overlay height, only applied to [`Prank3dHudLayout::Horizontal`].
     pub height: Val,
 
+    /// Space between the overlay and the edges of the viewport.
+    pub margin: UiRect,
+
+    /// Space between the edges of the overlay and its segments.
+    pub padding: UiRect,
+
+    /// Space between the segments.
+    pub gap: Val,
+
     /// Overlay background color.
     pub background_color: BackgroundColor,
 
     /// Overlay text style.
     pub text_style: TextStyle,
+
+    /// Text styles that override `text_style` for particular segments.
+    pub segment_text_styles: HashMap<Prank3dHudSegment, TextStyle>,
+
+    /// Segments of the main page of the overlay in order.
+    ///
+    /// Remove a segment to hide it, or add [`Prank3dHudSegment::Rotation`],
+    /// [`Prank3dHudSegment::Forward`] and [`Prank3dHudSegment::Heading`] to show the orientation.
+    pub segments: Vec<Prank3dHudSegment>,
+
+    /// Segments of the stats page of the overlay in order.
+    pub stats_segments: Vec<Prank3dHudSegment>,
+
+    /// Key to cycle the pages of the overlay with.
+    pub page_key: KeyCode,
+
+    /// Direction that [`Prank3dHudSegment::Heading`] considers north.
+    ///
+    /// Headings are measured clockwise around [`Vec3::Y`], so east is `compass_north` crossed with
+    /// [`Vec3::Y`].
+    pub compass_north: Vec3,
+
+    /// Number of frames that frame time statistics are gathered over.
+    pub fps_window: usize,
+
+    /// FPS below which [`Prank3dHudSegment::Fps`] is colored with `fps_warning_color`.
+    pub fps_warning_threshold: f32,
+
+    /// FPS below which [`Prank3dHudSegment::Fps`] is colored with `fps_critical_color`.
+    pub fps_critical_threshold: f32,
+
+    /// [`Prank3dHudSegment::Fps`] color when FPS is above `fps_warning_threshold`.
+    pub fps_good_color: Color,
+
+    /// [`Prank3dHudSegment::Fps`] color when FPS is between `fps_critical_threshold` and
+    /// `fps_warning_threshold`.
+    pub fps_warning_color: Color,
+
+    /// [`Prank3dHudSegment::Fps`] color when FPS is below `fps_critical_threshold`.
+    pub fps_critical_color: Color,
+
+    /// Key to toggle the visibility of the overlay with.
+    pub visibility_key: KeyCode,
+
+    /// Whether to only show the overlay while flying or offsetting.
+    pub moving_only: bool,
+
+    /// Seconds without any input after which the overlay starts fading out.
+    ///
+    /// Set `None` to never fade out.
+    pub fade_delay: Option<f32>,
+
+    /// Seconds that the overlay takes to fade out.
+    pub fade_duration: f32,
+
+    /// Whether to show a crosshair at the center of the viewport.
+    pub crosshair_enabled: bool,
+
+    /// Crosshair width and height.
+    pub crosshair_size: f32,
+
+    /// Crosshair line thickness.
+    pub crosshair_thickness: f32,
+
+    /// Crosshair color.
+    pub crosshair_color: Color,
 }
 
 impl Default for Prank3dHudConfig {
     fn default() -> Self {
         Self {
+            anchor: Prank3dHudAnchor::Bottom,
+            layout: Prank3dHudLayout::Horizontal,
             height: Val::Px(25.0),
+            margin: UiRect::all(Val::Px(0.0)),
+            padding: UiRect::horizontal(Val::Px(5.0)),
+            gap: Val::Px(20.0),
             background_color: Color::BLACK.with_a(0.5).into(),
             text_style: TextStyle {
                 font_size: 14.0,
                 color: Color::WHITE,
                 ..default()
             },
+            segment_text_styles: HashMap::default(),
+            segments: vec![
+                Prank3dHudSegment::Translation,
+                Prank3dHudSegment::Fps,
+                Prank3dHudSegment::Fov,
+                Prank3dHudSegment::Speed,
+                Prank3dHudSegment::Collision,
+                Prank3dHudSegment::Picked,
+                Prank3dHudSegment::Aimed,
+            ],
+            stats_segments: vec![
+                Prank3dHudSegment::Entities,
+                Prank3dHudSegment::Meshes,
+                Prank3dHudSegment::Lights,
+                Prank3dHudSegment::Cameras,
+                Prank3dHudSegment::Assets,
+            ],
+            page_key: KeyCode::F3,
+            compass_north: Vec3::NEG_Z,
+            fps_window: 120,
+            fps_warning_threshold: 55.0,
+            fps_critical_threshold: 30.0,
+            fps_good_color: Color::GREEN,
+            fps_warning_color: Color::YELLOW,
+            fps_critical_color: Color::RED,
+            visibility_key: KeyCode::H,
+            moving_only: false,
+            fade_delay: None,
+            fade_duration: 0.5,
+            crosshair_enabled: false,
+            crosshair_size: 12.0,
+            crosshair_thickness: 2.0,
+            crosshair_color: Color::WHITE.with_a(0.75),
         }
     }
 }
 
+/// Placement of the three-dimensional camera HUD overlay within the viewport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Prank3dHudAnchor {
+    /// Spans the top edge.
+    Top,
+
+    /// Spans the bottom edge.
+    Bottom,
+
+    /// Sits in the top left corner.
+    TopLeft,
+
+    /// Sits in the top right corner.
+    TopRight,
+
+    /// Sits in the bottom left corner.
+    BottomLeft,
+
+    /// Sits in the bottom right corner.
+    BottomRight,
+}
+
+/// Direction that the segments of the three-dimensional camera HUD overlay are laid out in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Prank3dHudLayout {
+    /// Segments are laid out in a single row.
+    Horizontal,
+
+    /// Segments are laid out in a single column, like a panel.
+    Vertical,
+}
+
+/// Segment of the three-dimensional camera HUD overlay.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum Prank3dHudSegment {
+    /// Translation of the [`Prank3d`].
+    Translation,
+
+    /// Yaw, pitch and roll of the [`Prank3d`] in degrees.
+    Rotation,
+
+    /// Forward vector of the [`Prank3d`].
+    Forward,
+
+    /// Compass heading of the [`Prank3d`] relative to `compass_north` field of
+    /// [`Prank3dHudConfig`].
+    Heading,
+
+    /// Smoothed frames per second, frame time, 1% low FPS and maximum frame time.
+    Fps,
+
+    /// Field of view, or scale of orthographic projection, of the [`Prank3d`].
+    Fov,
+
+    /// Speed scalar of the [`Prank3d`] and its modifiers.
+    Speed,
+
+    /// Whether collision of the [`Prank3d`] is enabled.
+    Collision,
+
+    /// The entity that was last picked.
+    Picked,
+
+    /// The entity under the center of view of the [`Prank3d`].
+    Aimed,
+
+    /// Number of entities.
+    Entities,
+
+    /// Number of visible meshes.
+    Meshes,
+
+    /// Number of point, spot and directional lights.
+    Lights,
+
+    /// Number of cameras, and how many of them are active.
+    Cameras,
+
+    /// Number of loaded mesh, material and image assets.
+    Assets,
+
+    /// Numbered list of the cameras of [`Prank3dSwitcher`](super::switcher::Prank3dSwitcher).
+    ///
+    /// Appended to the main page unless `segments` field of [`Prank3dHudConfig`] lists it, and
+    /// empty while there is no switcher.
+    Switcher,
+
+    /// Segment registered with [`Prank3dHudAppExt::add_prank_hud_segment`] under this name.
+    Custom(String),
+}
+
+/// Adds custom segments to the three-dimensional camera HUD overlay.
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// fn distance_to_origin(In(prank): In<Entity>, transforms: Query<&GlobalTransform>) -> String {
+///     let transform = transforms.get(prank).unwrap();
+///     format!("Origin: {:.2}m", transform.translation().length())
+/// }
+///
+/// App::new()
+///     .add_plugins((DefaultPlugins, PrankPlugin::default()))
+///     .add_prank_hud_segment("distance_to_origin", distance_to_origin)
+///     .run();
+/// ```
+pub trait Prank3dHudAppExt {
+    /// Registers `system` as the [`Prank3dHudSegment::Custom`] segment of `name`, whose text is
+    /// the output of `system` given the [`Prank3d`] entity of the overlay every frame.
+    ///
+    /// The segment is appended to the main page unless `segments` field of [`Prank3dHudConfig`]
+    /// lists it, which places it in that order instead.
+    fn add_prank_hud_segment<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<Entity, String, M>,
+    ) -> &mut Self;
+}
+
+impl Prank3dHudAppExt for App {
+    fn add_prank_hud_segment<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<Entity, String, M>,
+    ) -> &mut Self {
+        let segment = Prank3dHudSegment::Custom(name.into());
+        register_segment(&mut self.world, segment.clone(), system);
+        append_segment(&mut self.world, segment);
+
+        self
+    }
+}
+
+/// Registered [`Prank3dHudSegment`]s.
+#[derive(Default, Resource)]
+struct HudSegments {
+    /// Systems that produce the text of every segment.
+    systems: HashMap<Prank3dHudSegment, BoxedSystem<Entity, String>>,
+
+    /// Segments that are appended to the main page unless `segments` field of
+    /// [`Prank3dHudConfig`] lists them.
+    appended: Vec<Prank3dHudSegment>,
+}
+
+pub(super) fn register_segment<M>(
+    world: &mut World,
+    segment: Prank3dHudSegment,
+    system: impl IntoSystem<Entity, String, M>,
+) {
+    let mut system = IntoSystem::into_system(system);
+    system.initialize(world);
+
+    world
+        .get_resource_or_insert_with(HudSegments::default)
+        .systems
+        .insert(segment, Box::new(system));
+}
+
+/// Appends `segment` to the main page unless `segments` field of [`Prank3dHudConfig`] lists it.
+pub(super) fn append_segment(world: &mut World, segment: Prank3dHudSegment) {
+    let appended = &mut world
+        .get_resource_or_insert_with(HudSegments::default)
+        .appended;
+    if !appended.contains(&segment) {
+        appended.push(segment);
+    }
+}
+
+/// Root of the overlay of a [`Prank3d`], covering its viewport.
 #[derive(Component)]
-struct Hud;
+struct Hud(Entity);
 
+/// The [`Camera`] that draws the overlays of every [`Prank3d`] across the whole window.
+///
+/// Every [`Camera`] that draws UI draws all of it within its own viewport, so the overlays are
+/// drawn once by this instead of by each [`Prank3d`].
 #[derive(Component)]
-struct HudTranslation;
+struct HudCamera;
 
+/// [`UiCameraConfig`] of a [`Prank3d`] from before its UI was hidden in favor of [`HudCamera`].
 #[derive(Component)]
-struct HudFps;
+struct HudHiddenUi(Option<UiCameraConfig>);
 
 #[derive(Component)]
-struct HudFov;
+struct HudText(Entity, Prank3dHudSegment);
 
+/// Opacity of the overlays, along with the state that determines it.
+#[derive(Resource)]
+struct HudOpacity {
+    opacity: f32,
+    hidden: bool,
+    idle_seconds: f32,
+}
+
+impl Default for HudOpacity {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            hidden: false,
+            idle_seconds: 0.0,
+        }
+    }
+}
+
+/// Alpha of the color of an overlay node at full opacity.
 #[derive(Component)]
-struct HudSpeed;
+struct HudAlpha(f32);
+
+/// Overlay configuration that the overlays are spawned with.
+#[derive(Default, Resource)]
+struct AppliedConfig(Option<Prank3dHudConfig>);
 
-fn just_activated(active: Res<Prank3dActive>) -> bool {
-    active.is_changed() && active.is_some()
+/// Page of the overlay that is currently shown.
+#[derive(Default, Resource)]
+enum HudPage {
+    #[default]
+    Main,
+    Stats,
 }
 
-fn just_inactivated(active: Res<Prank3dActive>) -> bool {
-    active.is_changed() && active.is_none()
+impl HudPage {
+    /// Segments of the page in order, including the appended ones of `hud_segments`.
+    fn segments(
+        &self,
+        config: &Prank3dHudConfig,
+        hud_segments: &HudSegments,
+    ) -> Vec<Prank3dHudSegment> {
+        match self {
+            Self::Main => config
+                .segments
+                .iter()
+                .chain(
+                    hud_segments
+                        .appended
+                        .iter()
+                        .filter(|segment| !config.segments.contains(segment)),
+                )
+                .cloned()
+                .collect(),
+            Self::Stats => config.stats_segments.clone(),
+        }
+    }
 }
 
-fn spawn(mut commands: Commands, hud: Query<(), With<Hud>>, config: Res<PrankConfig>) {
-    if !hud.is_empty() {
-        return;
+/// The entity under the center of view of every [`Prank3d`] and its distance.
+#[derive(Default, Resource)]
+struct Aimed(HashMap<Entity, (Entity, f32)>);
+
+/// Durations of the most recent frames in seconds.
+#[derive(Default, Resource)]
+struct FrameTimes(VecDeque<f32>);
+
+impl FrameTimes {
+    fn average(&self) -> f32 {
+        self.0.iter().sum::<f32>() / self.0.len().max(1) as f32
+    }
+
+    fn max(&self) -> f32 {
+        self.0.iter().copied().fold(0.0, f32::max)
     }
-    let Some(config) = config.hud3d.clone() else {
+
+    /// Average duration of the slowest 1% of frames.
+    fn low(&self) -> f32 {
+        let mut frame_times = self.0.iter().copied().collect::<Vec<_>>();
+        frame_times.sort_by(|a, b| b.total_cmp(a));
+
+        let count = (frame_times.len() as f32 / 100.0).ceil().max(1.0) as usize;
+        frame_times.iter().take(count).sum::<f32>() / count as f32
+    }
+}
+
+/// Whether `prank` is rendered and thus gets an overlay.
+fn is_shown(camera: &Camera, prank: &Prank3d) -> bool {
+    camera.is_active && prank.is_active
+}
+
+fn spawn(
+    mut commands: Commands,
+    hud: Query<&Hud>,
+    pranks: Query<(Entity, &Camera, &Prank3d)>,
+    config: Res<PrankConfig>,
+    page: Res<HudPage>,
+    hud_segments: Res<HudSegments>,
+) {
+    let Some(config) = config.hud3d.as_ref() else {
         return;
     };
 
+    for (entity, camera, prank) in pranks.iter() {
+        if !is_shown(camera, prank) || hud.iter().any(|hud| hud.0 == entity) {
+            continue;
+        }
+
+        let segments = page.segments(config, &hud_segments);
+        spawn_hud(&mut commands, entity, camera, config, &segments);
+    }
+}
+
+fn spawn_hud(
+    commands: &mut Commands,
+    prank: Entity,
+    camera: &Camera,
+    config: &Prank3dHudConfig,
+    segments: &[Prank3dHudSegment],
+) {
+    let (top, bottom) = match config.anchor {
+        Prank3dHudAnchor::Top | Prank3dHudAnchor::TopLeft | Prank3dHudAnchor::TopRight => {
+            (Val::Px(0.0), Val::Auto)
+        }
+        Prank3dHudAnchor::Bottom | Prank3dHudAnchor::BottomLeft | Prank3dHudAnchor::BottomRight => {
+            (Val::Auto, Val::Px(0.0))
+        }
+    };
+    let (left, right, width) = match config.anchor {
+        Prank3dHudAnchor::Top | Prank3dHudAnchor::Bottom => {
+            (Val::Px(0.0), Val::Auto, Val::Percent(100.0))
+        }
+        Prank3dHudAnchor::TopLeft | Prank3dHudAnchor::BottomLeft => {
+            (Val::Px(0.0), Val::Auto, Val::Auto)
+        }
+        Prank3dHudAnchor::TopRight | Prank3dHudAnchor::BottomRight => {
+            (Val::Auto, Val::Px(0.0), Val::Auto)
+        }
+    };
+    let (flex_direction, height, align_items) = match config.layout {
+        Prank3dHudLayout::Horizontal => (FlexDirection::Row, config.height, AlignItems::Center),
+        Prank3dHudLayout::Vertical => (FlexDirection::Column, Val::Auto, AlignItems::Start),
+    };
+
+    let [root_left, root_top, root_width, root_height] =
+        viewport_rect(camera).unwrap_or([Val::Auto; 4]);
     commands
         .spawn((
             Name::new("Hud"),
-            Hud,
+            Hud(prank),
             NodeBundle {
-                background_color: config.background_color,
                 style: Style {
                     position_type: PositionType::Absolute,
-                    bottom: Val::Px(0.0),
-                    width: Val::Vw(100.0),
-                    height: config.height,
-                    padding: UiRect::horizontal(Val::Px(5.0)),
-                    column_gap: Val::Px(20.0),
-                    align_items: AlignItems::Center,
+                    left: root_left,
+                    top: root_top,
+                    width: root_width,
+                    height: root_height,
                     ..default()
                 },
                 ..default()
             },
         ))
         .with_children(|parent| {
-            parent.spawn((
-                Name::new("HudTranslation"),
-                HudTranslation,
-                TextBundle::from_section("", config.text_style.clone()),
-            ));
+            parent
+                .spawn((
+                    Name::new("HudBar"),
+                    HudAlpha(config.background_color.0.a()),
+                    NodeBundle {
+                        background_color: config.background_color,
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top,
+                            bottom,
+                            left,
+                            right,
+                            width,
+                            height,
+                            margin: config.margin,
+                            padding: config.padding,
+                            flex_direction,
+                            column_gap: config.gap,
+                            row_gap: config.gap,
+                            align_items,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    for segment in segments.iter() {
+                        let text_style = config
+                            .segment_text_styles
+                            .get(segment)
+                            .unwrap_or(&config.text_style);
 
-            parent.spawn((
-                Name::new("HudFps"),
-                HudFps,
-                TextBundle::from_section("", config.text_style.clone()),
-            ));
+                        parent.spawn((
+                            Name::new(format!("Hud{:?}", segment)),
+                            HudText(prank, segment.clone()),
+                            HudAlpha(text_style.color.a()),
+                            TextBundle::from_section("", text_style.clone()),
+                        ));
+                    }
+                });
 
-            parent.spawn((
-                Name::new("HudFov"),
-                HudFov,
-                TextBundle::from_section("", config.text_style.clone()),
-            ));
-
-            parent.spawn((
-                Name::new("HudSpeed"),
-                HudSpeed,
-                TextBundle::from_section("", config.text_style.clone()),
-            ));
+            if config.crosshair_enabled {
+                parent
+                    .spawn((
+                        Name::new("HudCrosshair"),
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(50.0),
+                                top: Val::Percent(50.0),
+                                margin: UiRect {
+                                    left: Val::Px(-0.5 * config.crosshair_size),
+                                    top: Val::Px(-0.5 * config.crosshair_size),
+                                    ..default()
+                                },
+                                width: Val::Px(config.crosshair_size),
+                                height: Val::Px(config.crosshair_size),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .with_children(|parent| {
+                        for (width, height) in [
+                            (config.crosshair_size, config.crosshair_thickness),
+                            (config.crosshair_thickness, config.crosshair_size),
+                        ] {
+                            parent.spawn((
+                                HudAlpha(config.crosshair_color.a()),
+                                NodeBundle {
+                                    background_color: config.crosshair_color.into(),
+                                    style: Style {
+                                        position_type: PositionType::Absolute,
+                                        width: Val::Px(width),
+                                        height: Val::Px(height),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                            ));
+                        }
+                    });
+            }
         });
 }
 
-fn despawn(mut commands: Commands, hud: Query<Entity, With<Hud>>) {
-    let Ok(entity) = hud.get_single() else {
+fn despawn(
+    mut commands: Commands,
+    hud: Query<(Entity, &Hud)>,
+    pranks: Query<(&Camera, &Prank3d)>,
+    active: Res<Prank3dActive>,
+    applied_config: Res<AppliedConfig>,
+    page: Res<HudPage>,
+) {
+    let outdated = applied_config.is_changed() || page.is_changed();
+    for (entity, hud) in hud.iter() {
+        let shown = !outdated
+            && applied_config.0.is_some()
+            && active.is_some()
+            && pranks
+                .get(hud.0)
+                .is_ok_and(|(camera, prank)| is_shown(camera, prank));
+        if !shown {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn cycle_page(mut page: ResMut<HudPage>, config: Res<PrankConfig>, keyboard: Res<Input<KeyCode>>) {
+    let Some(config) = config.hud3d.as_ref() else {
         return;
     };
+    if !keyboard.just_pressed(config.page_key) {
+        return;
+    }
 
-    commands.entity(entity).despawn_recursive();
+    *page = match *page {
+        HudPage::Main => HudPage::Stats,
+        HudPage::Stats => HudPage::Main,
+    };
 }
 
-fn sync_translation(
-    mut hud_translation: Query<&mut Text, With<HudTranslation>>,
-    active: Res<Prank3dActive>,
-    pranks: Query<&Prank3d>,
+fn toggle_visibility(
+    mut opacity: ResMut<HudOpacity>,
+    config: Res<PrankConfig>,
+    keyboard: Res<Input<KeyCode>>,
 ) {
-    let Ok(mut text) = hud_translation.get_single_mut() else {
+    let Some(config) = config.hud3d.as_ref() else {
         return;
     };
-    let prank = pranks.get(active.expect("is active")).expect("exists");
 
-    let Vec3 { x, y, z } = prank.translation;
-    text.sections[0].value = format!("Translation: [{:.2}, {:.2}, {:.2}]", x, y, z);
+    if keyboard.just_pressed(config.visibility_key) {
+        opacity.hidden = !opacity.hidden;
+    }
 }
 
-fn sync_fps(mut hud_fps: Query<&mut Text, With<HudFps>>, time: Res<Time>) {
-    let Ok(mut text) = hud_fps.get_single_mut() else {
+/// Determines the opacity of the overlays from their visibility, the state and input activity.
+#[allow(clippy::too_many_arguments)]
+fn fade(
+    mut opacity: ResMut<HudOpacity>,
+    config: Res<PrankConfig>,
+    state: Res<State<Prank3dState>>,
+    capturing: Res<Capturing>,
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+) {
+    let Some(config) = config.hud3d.as_ref() else {
         return;
     };
 
-    text.sections[0].value = format!("FPS: {:.0}", time.delta_seconds().recip());
+    let is_idle = keyboard.get_pressed().len() == 0
+        && mouse.get_pressed().len() == 0
+        && motion.iter().count() == 0
+        && wheel.iter().count() == 0;
+    if is_idle {
+        opacity.idle_seconds += time.delta_seconds();
+    } else {
+        opacity.idle_seconds = 0.0;
+    }
+
+    let is_moving = matches!(**state, Prank3dState::Fly | Prank3dState::Offset);
+    let is_hidden = opacity.hidden || capturing.hide_overlays || (config.moving_only && !is_moving);
+    let value = if is_hidden {
+        0.0
+    } else {
+        match config.fade_delay {
+            Some(fade_delay) => {
+                let faded =
+                    (opacity.idle_seconds - fade_delay) / config.fade_duration.max(f32::EPSILON);
+                1.0 - faded.clamp(0.0, 1.0)
+            }
+            None => 1.0,
+        }
+    };
+
+    if opacity.opacity != value {
+        opacity.opacity = value;
+    }
 }
 
-fn sync_fov(
-    mut hud_fov: Query<&mut Text, With<HudFov>>,
+fn sync_opacity(
+    mut hud: Query<&mut Visibility, With<Hud>>,
+    mut nodes: Query<(
+        &HudAlpha,
+        Option<&mut BackgroundColor>,
+        Option<&mut Text>,
+        Option<&HudText>,
+    )>,
+    opacity: Res<HudOpacity>,
+) {
+    let visibility = if opacity.opacity > 0.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut hud_visibility in hud.iter_mut() {
+        if *hud_visibility != visibility {
+            *hud_visibility = visibility;
+        }
+    }
+
+    for (alpha, background_color, text, hud_text) in nodes.iter_mut() {
+        let a = alpha.0 * opacity.opacity;
+        if hud_text.is_some_and(|hud_text| hud_text.1 == Prank3dHudSegment::Fps) {
+            continue;
+        }
+
+        if let Some(mut text) = text {
+            if text.sections[0].style.color.a() != a {
+                text.sections[0].style.color.set_a(a);
+            }
+        } else if let Some(mut background_color) = background_color {
+            if background_color.0.a() != a {
+                background_color.0.set_a(a);
+            }
+        }
+    }
+}
+
+/// Keeps track of the overlay configuration that the overlays were spawned with, so that they are
+/// respawned as soon as it changes.
+fn sync_config(mut applied_config: ResMut<AppliedConfig>, config: Res<PrankConfig>) {
+    if applied_config.0.reflect_partial_eq(&config.hud3d) != Some(true) {
+        applied_config.0 = config.hud3d.clone();
+    }
+}
+
+/// Draws the overlays with [`HudCamera`] instead of the [`Prank3d`]s that they belong to.
+fn sync_camera(
+    mut commands: Commands,
+    mut hud_camera: Query<(Entity, &mut Camera), With<HudCamera>>,
+    pranks: Query<
+        (
+            Entity,
+            &Camera,
+            &Prank3d,
+            Option<&UiCameraConfig>,
+            Option<&HudHiddenUi>,
+        ),
+        Without<HudCamera>,
+    >,
     active: Res<Prank3dActive>,
-    pranks: Query<&Projection, With<Prank3d>>,
+    applied_config: Res<AppliedConfig>,
 ) {
-    let Ok(mut text) = hud_fov.get_single_mut() else {
+    let mut overlaid = None;
+    for (entity, camera, prank, ui_camera_config, hidden_ui) in pranks.iter() {
+        let has_hud = applied_config.0.is_some() && active.is_some() && is_shown(camera, prank);
+        match (has_hud, hidden_ui) {
+            (true, None) => {
+                commands.entity(entity).insert((
+                    HudHiddenUi(ui_camera_config.cloned()),
+                    UiCameraConfig { show_ui: false },
+                ));
+            }
+            (false, Some(HudHiddenUi(ui_camera_config))) => {
+                let mut entity = commands.entity(entity);
+                entity.remove::<HudHiddenUi>();
+                match ui_camera_config {
+                    Some(ui_camera_config) => entity.insert(ui_camera_config.clone()),
+                    None => entity.remove::<UiCameraConfig>(),
+                };
+            }
+            _ => {}
+        }
+
+        if has_hud && overlaid.is_none() {
+            overlaid = Some(camera);
+        }
+    }
+
+    let Some(prank) = overlaid else {
+        for (entity, _) in hud_camera.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
         return;
     };
-    let projection = pranks.get(active.expect("is active")).expect("exists");
 
-    text.sections[0].value = match projection {
-        Projection::Perspective(projection) => format!("FOV: {:.0}", projection.fov.to_degrees()),
-        Projection::Orthographic(projection) => format!("SCALE: {:.2}", projection.scale),
+    let camera = Camera {
+        order: isize::MAX,
+        target: prank.target.clone(),
+        hdr: prank.hdr,
+        ..default()
     };
+    match hud_camera.get_single_mut() {
+        Ok((_, mut hud_camera)) => {
+            if hud_camera.hdr != camera.hdr
+                || hud_camera.target.normalize(None) != camera.target.normalize(None)
+            {
+                *hud_camera = camera;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Name::new("HudCamera"),
+                HudCamera,
+                Prank3dGizmoHidden,
+                RenderLayers::none(),
+                Camera2dBundle {
+                    camera,
+                    camera_2d: Camera2d {
+                        clear_color: ClearColorConfig::None,
+                    },
+                    ..default()
+                },
+            ));
+        }
+    }
 }
 
-fn sync_speed(
-    mut hud_speed: Query<&mut Text, With<HudSpeed>>,
-    active: Res<Prank3dActive>,
-    pranks: Query<&Prank3d>,
+/// Fits the overlay of every [`Prank3d`] to its viewport.
+fn sync_viewport(mut hud: Query<(&Hud, &mut Style)>, pranks: Query<&Camera, With<Prank3d>>) {
+    for (hud, mut style) in hud.iter_mut() {
+        let Some([left, top, width, height]) = pranks.get(hud.0).ok().and_then(viewport_rect)
+        else {
+            continue;
+        };
+
+        if style.left != left || style.top != top || style.width != width || style.height != height
+        {
+            style.left = left;
+            style.top = top;
+            style.width = width;
+            style.height = height;
+        }
+    }
+}
+
+/// Left, top, width and height of the viewport of `camera`.
+///
+/// The overlays are laid out in window coordinates, since [`HudCamera`] covers the whole window.
+fn viewport_rect(camera: &Camera) -> Option<[Val; 4]> {
+    let viewport = camera.logical_viewport_rect()?;
+
+    Some([
+        Val::Px(viewport.min.x),
+        Val::Px(viewport.min.y),
+        Val::Px(viewport.width()),
+        Val::Px(viewport.height()),
+    ])
+}
+
+/// Whether the entity under the crosshair is shown.
+fn aiming(config: Res<PrankConfig>, page: Res<HudPage>, segments: Res<HudSegments>) -> bool {
+    config.hud3d.as_ref().is_some_and(|config| {
+        config.crosshair_enabled
+            && page
+                .segments(config, &segments)
+                .contains(&Prank3dHudSegment::Aimed)
+    })
+}
+
+fn aim(
+    mut aimed: ResMut<Aimed>,
+    hud: Query<&Hud>,
+    pranks: Query<(&Camera, &GlobalTransform, &Prank3d)>,
+    targets: Query<(Entity, &Aabb, &GlobalTransform, Option<&Handle<Mesh>>)>,
+    meshes: Res<Assets<Mesh>>,
 ) {
-    let Ok(mut text) = hud_speed.get_single_mut() else {
+    aimed.0.clear();
+    for Hud(entity) in hud.iter() {
+        let Ok((camera, transform, prank)) = pranks.get(*entity) else {
+            continue;
+        };
+        let hit = camera
+            .logical_viewport_size()
+            .and_then(|size| camera.viewport_to_world(transform, 0.5 * size))
+            .and_then(|ray| cast_targets(ray, targets.iter(), &meshes, *entity, prank.pick_precise))
+            .map(|hit| {
+                let distance = hit.point.distance(transform.translation());
+                (hit.entity, distance)
+            });
+
+        if let Some(hit) = hit {
+            aimed.0.insert(*entity, hit);
+        }
+    }
+}
+
+fn sync_segments(world: &mut World) {
+    let texts = world
+        .query::<(Entity, &HudText)>()
+        .iter(world)
+        .map(|(entity, text)| (entity, text.0, text.1.clone()))
+        .collect::<Vec<_>>();
+
+    world.resource_scope(|world, mut segments: Mut<HudSegments>| {
+        for (entity, prank, segment) in texts {
+            if world.get::<Prank3d>(prank).is_none() {
+                continue;
+            }
+            let Some(system) = segments.systems.get_mut(&segment) else {
+                continue;
+            };
+
+            let value = system.run(prank, world);
+            system.apply_deferred(world);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                text.sections[0].value = value;
+            }
+        }
+    });
+}
+
+fn translation(In(entity): In<Entity>, pranks: Query<&Prank3d>) -> String {
+    let prank = pranks.get(entity).expect("exists");
+
+    let Vec3 { x, y, z } = prank.translation;
+    format!("Translation: [{:.2}, {:.2}, {:.2}]", x, y, z)
+}
+
+fn rotation(In(entity): In<Entity>, pranks: Query<&Transform, With<Prank3d>>) -> String {
+    let transform = pranks.get(entity).expect("exists");
+
+    let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    format!(
+        "Yaw: {:.1} Pitch: {:.1} Roll: {:.1}",
+        yaw.to_degrees(),
+        pitch.to_degrees(),
+        roll.to_degrees(),
+    )
+}
+
+fn forward(In(entity): In<Entity>, pranks: Query<&Transform, With<Prank3d>>) -> String {
+    let transform = pranks.get(entity).expect("exists");
+
+    let Vec3 { x, y, z } = transform.forward();
+    format!("Forward: [{:.2}, {:.2}, {:.2}]", x, y, z)
+}
+
+fn heading(
+    In(entity): In<Entity>,
+    pranks: Query<&Transform, With<Prank3d>>,
+    config: Res<PrankConfig>,
+) -> String {
+    const CARDINALS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+    let transform = pranks.get(entity).expect("exists");
+    let Some(config) = config.hud3d.as_ref() else {
+        return String::new();
+    };
+
+    let north = config.compass_north;
+    let north = (north - north.y * Vec3::Y).normalize_or_zero();
+    let east = north.cross(Vec3::Y);
+    let forward = transform.forward();
+    let heading = forward
+        .dot(east)
+        .atan2(forward.dot(north))
+        .to_degrees()
+        .rem_euclid(360.0);
+
+    let cardinal = CARDINALS[(heading / 45.0).round() as usize % CARDINALS.len()];
+    format!("Heading: {:.0}° {}", heading, cardinal)
+}
+
+fn measure_frame_time(
+    mut frame_times: ResMut<FrameTimes>,
+    time: Res<Time>,
+    config: Res<PrankConfig>,
+) {
+    let Some(config) = config.hud3d.as_ref() else {
         return;
     };
-    let prank = pranks.get(active.expect("is active")).expect("exists");
 
-    text.sections[0].value = format!("Speed Scalar: {:.1}", prank.speed_scalar);
+    // The first frame has no duration.
+    if time.delta_seconds() <= 0.0 {
+        return;
+    }
+
+    frame_times.0.push_back(time.delta_seconds());
+    while frame_times.0.len() > config.fps_window.max(1) {
+        frame_times.0.pop_front();
+    }
+}
+
+fn fps(In(_): In<Entity>, frame_times: Res<FrameTimes>) -> String {
+    if frame_times.0.is_empty() {
+        return "FPS: -".to_string();
+    }
+
+    format!(
+        "FPS: {:.0} ({:.2}ms) 1% Low: {:.0} Max: {:.2}ms",
+        frame_times.average().recip(),
+        1000.0 * frame_times.average(),
+        frame_times.low().recip(),
+        1000.0 * frame_times.max(),
+    )
+}
+
+fn sync_fps_color(
+    mut hud_texts: Query<(&HudText, &mut Text)>,
+    frame_times: Res<FrameTimes>,
+    config: Res<PrankConfig>,
+    opacity: Res<HudOpacity>,
+) {
+    let Some(config) = config.hud3d.as_ref() else {
+        return;
+    };
+
+    let fps = frame_times.average().recip();
+    let color = if fps < config.fps_critical_threshold {
+        config.fps_critical_color
+    } else if fps < config.fps_warning_threshold {
+        config.fps_warning_color
+    } else {
+        config.fps_good_color
+    };
+    let color = color.with_a(color.a() * opacity.opacity);
+
+    for (hud_text, mut text) in hud_texts.iter_mut() {
+        if hud_text.1 == Prank3dHudSegment::Fps && text.sections[0].style.color != color {
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+fn fov(In(entity): In<Entity>, pranks: Query<&Projection, With<Prank3d>>) -> String {
+    let projection = pranks.get(entity).expect("exists");
+
+    match projection {
+        Projection::Perspective(projection) => format!("FOV: {:.0}", projection.fov.to_degrees()),
+        Projection::Orthographic(projection) => format!("SCALE: {:.2}", projection.scale),
+    }
+}
+
+fn speed(In(entity): In<Entity>, pranks: Query<&Prank3d>, keyboard: Res<Input<KeyCode>>) -> String {
+    let prank = pranks.get(entity).expect("exists");
+
+    let modifier = prank.speed_modifier(&keyboard);
+    if modifier == 1.0 {
+        format!("Speed Scalar: {:.1}", prank.speed_scalar)
+    } else {
+        format!("Speed Scalar: {:.1} (x{:.2})", prank.speed_scalar, modifier)
+    }
+}
+
+fn collision(In(entity): In<Entity>, pranks: Query<&Prank3d>) -> String {
+    let prank = pranks.get(entity).expect("exists");
+
+    format!("Collision: {}", if prank.collision { "On" } else { "Off" })
+}
+
+fn picked(In(_): In<Entity>, picked: Res<Picked>, names: Query<&Name>) -> String {
+    match **picked {
+        Some(entity) => match names.get(entity) {
+            Ok(name) => format!("Picked: {}", name),
+            Err(_) => format!("Picked: {:?}", entity),
+        },
+        None => "Picked: None".to_string(),
+    }
+}
+
+fn aimed(In(entity): In<Entity>, aimed: Res<Aimed>, names: Query<&Name>) -> String {
+    match aimed.0.get(&entity).copied() {
+        Some((entity, distance)) => match names.get(entity) {
+            Ok(name) => format!("Aimed: {} ({:?}) {:.2}m", name, entity, distance),
+            Err(_) => format!("Aimed: {:?} {:.2}m", entity, distance),
+        },
+        None => "Aimed: None".to_string(),
+    }
+}
+
+fn entities(In(_): In<Entity>, entities: Query<()>) -> String {
+    format!("Entities: {}", entities.iter().len())
+}
+
+fn meshes(In(_): In<Entity>, meshes: Query<&ComputedVisibility, With<Handle<Mesh>>>) -> String {
+    let visible = meshes
+        .iter()
+        .filter(|visibility| visibility.is_visible())
+        .count();
+
+    format!("Meshes: {}/{} visible", visible, meshes.iter().len())
+}
+
+fn lights(
+    In(_): In<Entity>,
+    point_lights: Query<(), With<PointLight>>,
+    spot_lights: Query<(), With<SpotLight>>,
+    directional_lights: Query<(), With<DirectionalLight>>,
+) -> String {
+    format!(
+        "Lights: {} point, {} spot, {} directional",
+        point_lights.iter().len(),
+        spot_lights.iter().len(),
+        directional_lights.iter().len(),
+    )
+}
+
+fn cameras(In(_): In<Entity>, cameras: Query<&Camera>) -> String {
+    let active = cameras.iter().filter(|camera| camera.is_active).count();
+
+    format!("Cameras: {}/{} active", active, cameras.iter().len())
+}
+
+fn assets(
+    In(_): In<Entity>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+    images: Res<Assets<Image>>,
+) -> String {
+    format!(
+        "Assets: {} meshes, {} materials, {} images",
+        meshes.len(),
+        materials.len(),
+        images.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_times_average_and_max() {
+        let frame_times = FrameTimes(VecDeque::from([0.01, 0.02, 0.03]));
+
+        assert!((frame_times.average() - 0.02).abs() < 1e-6);
+        assert_eq!(frame_times.max(), 0.03);
+    }
+
+    #[test]
+    fn frame_times_low_averages_slowest_percent() {
+        let mut frame_times = FrameTimes(VecDeque::from(vec![0.01; 198]));
+        frame_times.0.push_back(0.1);
+        frame_times.0.push_front(0.05);
+
+        assert!((frame_times.low() - 0.075).abs() < 1e-6);
+    }
+
+    #[test]
+    fn frame_times_low_takes_at_least_one_frame() {
+        let frame_times = FrameTimes(VecDeque::from([0.01, 0.04, 0.02]));
+
+        assert_eq!(frame_times.low(), 0.04);
+    }
+
+    #[test]
+    fn frame_times_empty() {
+        let frame_times = FrameTimes::default();
+
+        assert_eq!(frame_times.average(), 0.0);
+        assert_eq!(frame_times.low(), 0.0);
+        assert_eq!(frame_times.max(), 0.0);
+    }
+
+    #[test]
+    fn hud_camera_replaces_prank_ui() {
+        let mut world = World::new();
+        world.insert_resource(AppliedConfig(Some(Prank3dHudConfig::default())));
+        let user_config = UiCameraConfig { show_ui: true };
+        let prank = world
+            .spawn((Camera::default(), Prank3d::default(), user_config))
+            .id();
+        world.insert_resource(Prank3dActive(Some(prank)));
+
+        let mut sync = IntoSystem::into_system(sync_camera);
+        sync.initialize(&mut world);
+        sync.run((), &mut world);
+        sync.apply_deferred(&mut world);
+
+        let mut hud_cameras = world.query_filtered::<&Camera, With<HudCamera>>();
+        assert_eq!(hud_cameras.iter(&world).count(), 1);
+        assert!(!world.get::<UiCameraConfig>(prank).expect("exists").show_ui);
+
+        world.resource_mut::<Prank3dActive>().0 = None;
+        sync.run((), &mut world);
+        sync.apply_deferred(&mut world);
+
+        assert_eq!(hud_cameras.iter(&world).count(), 0);
+        assert!(world.get::<UiCameraConfig>(prank).expect("exists").show_ui);
+        assert!(world.get::<HudHiddenUi>(prank).is_none());
+    }
 }
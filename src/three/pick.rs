@@ -0,0 +1,286 @@
+//! Provides three-dimensional entity picking.
+
+use super::{
+    any_active, collision::cast_obb, cursor_position, Prank3d, Prank3dActive, Prank3dState,
+};
+use bevy::{
+    prelude::*,
+    render::{mesh::VertexAttributeValues, primitives::Aabb, render_resource::PrimitiveTopology},
+};
+use std::ops::Deref;
+
+pub(super) struct Prank3dPickPlugin;
+
+impl Plugin for Prank3dPickPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Prank3dPicked>()
+            .init_resource::<Picked>()
+            .add_systems(
+                Update,
+                pick.run_if(any_active.and_then(in_state(Prank3dState::None))),
+            );
+    }
+}
+
+/// Sent when an entity is picked with the cursor of the active [`Prank3d`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::three::pick::Prank3dPicked;
+/// #
+/// fn log_picked(mut picked: EventReader<Prank3dPicked>, names: Query<&Name>) {
+///     for picked in picked.iter() {
+///         if let Ok(name) = names.get(picked.entity) {
+///             info!("picked {} at {}", name, picked.point);
+///         }
+///     }
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(log_picked);
+/// ```
+#[derive(Clone, Copy, Debug, Event)]
+pub struct Prank3dPicked {
+    /// The entity that was picked.
+    pub entity: Entity,
+
+    /// World space point that was picked.
+    pub point: Vec3,
+
+    /// World space normal of the surface that was picked.
+    pub normal: Vec3,
+}
+
+/// The entity that was last picked.
+#[derive(Default, Resource)]
+pub(super) struct Picked(Option<Entity>);
+
+impl Deref for Picked {
+    type Target = Option<Entity>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+fn pick(world: &mut World) {
+    let entity = world.resource::<Prank3dActive>().expect("is active");
+    let prank = world.get::<Prank3d>(entity).expect("exists");
+    let precise = prank.pick_precise;
+    if !world
+        .resource::<Input<MouseButton>>()
+        .just_pressed(prank.pick_button)
+    {
+        return;
+    }
+
+    let Some(cursor) = cursor_position(world, entity) else {
+        return;
+    };
+    let camera = world.get::<Camera>(entity).expect("exists");
+    let transform = world.get::<GlobalTransform>(entity).expect("exists");
+    let Some(ray) = camera.viewport_to_world(transform, cursor) else {
+        return;
+    };
+
+    let picked = cast(world, ray, entity, precise);
+    world.resource_mut::<Picked>().0 = picked.map(|picked| picked.entity);
+    if let Some(picked) = picked {
+        world.send_event(picked);
+    }
+}
+
+/// Casts `ray` against the [`Aabb`] of every entity but `exclude`, and against their mesh
+/// triangles as well if `precise` is enabled.
+fn cast(world: &World, ray: Ray, exclude: Entity, precise: bool) -> Option<Prank3dPicked> {
+    let targets = world.iter_entities().filter_map(|entity| {
+        Some((
+            entity.id(),
+            entity.get::<Aabb>()?,
+            entity.get::<GlobalTransform>()?,
+            entity.get::<Handle<Mesh>>(),
+        ))
+    });
+
+    cast_targets(
+        ray,
+        targets,
+        world.resource::<Assets<Mesh>>(),
+        exclude,
+        precise,
+    )
+}
+
+/// Casts `ray` against the [`Aabb`] of every entity of `targets` but `exclude`, and against their
+/// mesh triangles as well if `precise` is enabled.
+pub(super) fn cast_targets<'a>(
+    ray: Ray,
+    targets: impl Iterator<
+        Item = (
+            Entity,
+            &'a Aabb,
+            &'a GlobalTransform,
+            Option<&'a Handle<Mesh>>,
+        ),
+    >,
+    meshes: &Assets<Mesh>,
+    exclude: Entity,
+    precise: bool,
+) -> Option<Prank3dPicked> {
+    targets
+        .filter(|(entity, ..)| *entity != exclude)
+        .filter_map(|(entity, aabb, transform, mesh)| {
+            let (distance, normal) = cast_obb(ray, aabb, transform, 0.0)?;
+
+            let mesh = mesh.and_then(|handle| meshes.get(handle));
+            let (distance, normal) = match mesh {
+                Some(mesh) if precise => cast_mesh(ray, mesh, transform)?,
+                _ => (distance, normal),
+            };
+
+            Some((entity, distance, normal))
+        })
+        .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+        .map(|(entity, distance, normal)| Prank3dPicked {
+            entity,
+            point: ray.get_point(distance),
+            normal,
+        })
+}
+
+/// Casts `ray` against the triangles of `mesh` transformed by `transform`.
+///
+/// Returns distance and world space normal of the closest triangle facing `ray`.
+pub(super) fn cast_mesh(ray: Ray, mesh: &Mesh, transform: &GlobalTransform) -> Option<(f32, Vec3)> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+
+    let inverse = transform.affine().inverse();
+    let origin = inverse.transform_point3(ray.origin);
+    let direction = inverse.transform_vector3(ray.direction);
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    let (distance, normal) = indices
+        .chunks_exact(3)
+        .filter_map(|triangle| {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]]
+                .map(|index| positions.get(index).copied().map(Vec3::from));
+            cast_triangle(origin, direction, [a?, b?, c?])
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))?;
+
+    let normal = inverse.matrix3.transpose().mul_vec3(normal).normalize();
+    Some((distance, normal))
+}
+
+/// Casts a ray from `origin` along `direction` against `triangle` with Möller–Trumbore algorithm.
+///
+/// Returns distance in units of `direction` and normal of `triangle` facing the ray.
+fn cast_triangle(origin: Vec3, direction: Vec3, [a, b, c]: [Vec3; 3]) -> Option<(f32, Vec3)> {
+    let ab = b - a;
+    let ac = c - a;
+    let p = direction.cross(ac);
+    let determinant = ab.dot(p);
+    if determinant.abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let inverse = determinant.recip();
+    let ao = origin - a;
+    let u = ao.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = ao.cross(ab);
+    let v = direction.dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let distance = ac.dot(q) * inverse;
+    if distance < 0.0 {
+        return None;
+    }
+
+    let normal = ab.cross(ac);
+    let normal = if normal.dot(direction) > 0.0 {
+        -normal
+    } else {
+        normal
+    };
+    Some((distance, normal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: [Vec3; 3] = [Vec3::ZERO, Vec3::X, Vec3::Y];
+
+    #[test]
+    fn triangle_hit_from_front() {
+        let (distance, normal) =
+            cast_triangle(Vec3::new(0.25, 0.25, 2.0), Vec3::NEG_Z, TRIANGLE).expect("hits");
+
+        assert!((distance - 2.0).abs() < 1e-6);
+        assert!(normal.normalize().abs_diff_eq(Vec3::Z, 1e-6));
+    }
+
+    #[test]
+    fn triangle_hit_from_back() {
+        let (distance, normal) =
+            cast_triangle(Vec3::new(0.25, 0.25, -2.0), Vec3::Z, TRIANGLE).expect("hits");
+
+        assert!((distance - 2.0).abs() < 1e-6);
+        assert!(normal.normalize().abs_diff_eq(Vec3::NEG_Z, 1e-6));
+    }
+
+    #[test]
+    fn triangle_hit_at_angle() {
+        let direction = Vec3::new(0.0, 1.0, -1.0).normalize();
+        let (distance, _) =
+            cast_triangle(Vec3::new(0.25, -0.75, 1.0), direction, TRIANGLE).expect("hits");
+
+        assert!((distance - 2.0_f32.sqrt()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn triangle_miss_outside() {
+        for origin in [
+            Vec3::new(0.75, 0.75, 1.0),
+            Vec3::new(-0.1, 0.5, 1.0),
+            Vec3::new(0.5, -0.1, 1.0),
+        ] {
+            assert!(cast_triangle(origin, Vec3::NEG_Z, TRIANGLE).is_none());
+        }
+    }
+
+    #[test]
+    fn triangle_miss_behind() {
+        assert!(cast_triangle(Vec3::new(0.25, 0.25, 1.0), Vec3::Z, TRIANGLE).is_none());
+    }
+
+    #[test]
+    fn triangle_miss_parallel() {
+        assert!(cast_triangle(Vec3::new(-1.0, 0.25, 0.0), Vec3::X, TRIANGLE).is_none());
+    }
+
+    #[test]
+    fn triangle_distance_in_units_of_direction() {
+        let (distance, _) =
+            cast_triangle(Vec3::new(0.25, 0.25, 2.0), 2.0 * Vec3::NEG_Z, TRIANGLE).expect("hits");
+
+        assert!((distance - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,478 @@
+//! Provides three-dimensional picture-in-picture camera preview.
+
+use super::{gizmo::Prank3dGizmoHidden, Prank3d, Prank3dActive};
+use crate::PrankConfig;
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode, Viewport},
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+};
+
+pub(super) struct Prank3dPipPlugin;
+
+impl Plugin for Prank3dPipPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Prank3dPipCorner>()
+            .init_resource::<Pip>()
+            .add_systems(
+                Update,
+                (
+                    cycle,
+                    resize,
+                    sync.after(cycle).after(resize),
+                    separate_gizmos.after(sync),
+                ),
+            );
+    }
+}
+
+/// Three-dimensional picture-in-picture camera preview configuration.
+///
+/// The preview renders the view of a [`Camera`] that is not a [`Prank3d`] into a corner of the
+/// viewport of the active [`Prank3d`].
+///
+/// Gizmos are moved to a render layer of their own while the preview is shown, so that they stay
+/// out of it. Only the active [`Prank3d`] is given that layer in the meantime.
+#[derive(Clone, Reflect)]
+pub struct Prank3dPipConfig {
+    /// Key to cycle the previewed [`Camera`] with.
+    ///
+    /// Cycling past the last [`Camera`] hides the preview. Cameras with
+    /// [`Prank3dGizmoHidden`] are skipped.
+    pub cycle_key: KeyCode,
+
+    /// Key to cycle the size of the preview through `sizes` with.
+    pub resize_key: KeyCode,
+
+    /// Widths of the preview relative to the viewport width.
+    pub sizes: Vec<f32>,
+
+    /// Corner of the viewport that the preview is placed in.
+    pub corner: Prank3dPipCorner,
+
+    /// Distance of the preview from the edges of the viewport in physical pixels.
+    pub margin: u32,
+
+    /// Color that the preview is cleared to.
+    pub background_color: Color,
+}
+
+impl Default for Prank3dPipConfig {
+    fn default() -> Self {
+        Self {
+            cycle_key: KeyCode::F5,
+            resize_key: KeyCode::F6,
+            sizes: vec![0.2, 0.3, 0.4],
+            corner: Prank3dPipCorner::TopRight,
+            margin: 10,
+            background_color: Color::BLACK,
+        }
+    }
+}
+
+/// Corner of the viewport that the picture-in-picture camera preview is placed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Prank3dPipCorner {
+    /// Top left corner.
+    TopLeft,
+
+    /// Top right corner.
+    TopRight,
+
+    /// Bottom left corner.
+    BottomLeft,
+
+    /// Bottom right corner.
+    BottomRight,
+}
+
+/// Render layer that gizmos are moved to while the preview is shown, so that they stay out of it.
+const GIZMO_LAYER: u8 = RenderLayers::TOTAL_LAYERS as u8 - 1;
+
+/// Render layer of the sprite that the preview is drawn with.
+const FRAME_LAYER: u8 = RenderLayers::TOTAL_LAYERS as u8 - 2;
+
+/// The [`Camera`] that renders the preview into an [`Image`].
+#[derive(Component)]
+struct PipCamera;
+
+/// The [`Camera`] that draws the [`Image`] of the preview into the viewport of the active
+/// [`Prank3d`].
+///
+/// Clearing a viewport clears the whole window, so the preview is cleared and rendered offscreen,
+/// then drawn as a sprite without clearing.
+#[derive(Component)]
+struct PipFrame;
+
+#[derive(Default, Resource)]
+struct Pip {
+    /// The [`Camera`] that is previewed.
+    source: Option<Entity>,
+
+    /// Index of the current size in `sizes` field of [`Prank3dPipConfig`].
+    size: usize,
+
+    /// [`Image`] that the preview is rendered into, while it is shown.
+    image: Option<Handle<Image>>,
+
+    /// Render layers of [`GizmoConfig`] from before gizmos were moved to [`GIZMO_LAYER`].
+    gizmo_layers: Option<RenderLayers>,
+}
+
+fn cycle(
+    mut pip: ResMut<Pip>,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    cameras: Query<Entity, (With<Camera>, Without<Prank3d>, Without<Prank3dGizmoHidden>)>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let Some(config) = config.pip3d.as_ref() else {
+        return;
+    };
+    if active.is_none() || !keyboard.just_pressed(config.cycle_key) {
+        return;
+    }
+
+    let mut sources = cameras.iter().collect::<Vec<_>>();
+    sources.sort();
+
+    pip.source = match pip.source {
+        Some(source) => sources.into_iter().find(|entity| *entity > source),
+        None => sources.first().copied(),
+    };
+}
+
+fn resize(
+    mut pip: ResMut<Pip>,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let Some(config) = config.pip3d.as_ref() else {
+        return;
+    };
+    if active.is_none() || !keyboard.just_pressed(config.resize_key) {
+        return;
+    }
+
+    pip.size = (pip.size + 1) % config.sizes.len().max(1);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sync(
+    mut commands: Commands,
+    mut pip_camera: Query<
+        (
+            Entity,
+            &mut Camera,
+            &mut Camera3d,
+            &mut Transform,
+            &mut Projection,
+            &mut RenderLayers,
+        ),
+        With<PipCamera>,
+    >,
+    mut pip_frame: Query<(Entity, &mut Camera), (With<PipFrame>, Without<PipCamera>)>,
+    mut pip: ResMut<Pip>,
+    mut images: ResMut<Assets<Image>>,
+    config: Res<PrankConfig>,
+    active: Res<Prank3dActive>,
+    pranks: Query<&Camera, (With<Prank3d>, Without<PipCamera>, Without<PipFrame>)>,
+    sources: Query<
+        (&GlobalTransform, Option<&Projection>, Option<&RenderLayers>),
+        (With<Camera>, Without<Prank3d>, Without<PipCamera>),
+    >,
+) {
+    let preview = config.pip3d.as_ref().and_then(|config| {
+        let prank = pranks.get((**active)?).ok()?;
+        let source = sources.get(pip.source?).ok()?;
+        let viewport = viewport(prank, config, pip.size)?;
+
+        Some((config, prank, source, viewport))
+    });
+
+    let Some((config, prank, (transform, projection, layers), viewport)) = preview else {
+        for (entity, ..) in pip_camera.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        for (entity, _) in pip_frame.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        pip.image = None;
+        return;
+    };
+
+    let size = Extent3d {
+        width: viewport.physical_size.x,
+        height: viewport.physical_size.y,
+        depth_or_array_layers: 1,
+    };
+    let image = match pip.image.as_ref() {
+        Some(image) => {
+            if let Some(image) = images.get_mut(image) {
+                if image.texture_descriptor.size != size {
+                    image.resize(size);
+                }
+            }
+            image.clone()
+        }
+        None => {
+            let image = images.add(offscreen_image(size));
+            pip.image = Some(image.clone());
+            image
+        }
+    };
+
+    let clear_color = ClearColorConfig::Custom(config.background_color);
+    let transform = transform.compute_transform();
+    let projection = projection.cloned().unwrap_or_default();
+    let layers = layers
+        .copied()
+        .unwrap_or_default()
+        .without(GIZMO_LAYER)
+        .without(FRAME_LAYER);
+    match pip_camera.get_single_mut() {
+        Ok((
+            _,
+            mut camera,
+            mut camera_3d,
+            mut pip_transform,
+            mut pip_projection,
+            mut pip_layers,
+        )) => {
+            if camera.order != prank.order + 1 {
+                camera.order = prank.order + 1;
+            }
+            if !matches!(camera_3d.clear_color, ClearColorConfig::Custom(color) if color == config.background_color)
+            {
+                camera_3d.clear_color = clear_color;
+            }
+            *pip_transform = transform;
+            *pip_projection = projection;
+            if *pip_layers != layers {
+                *pip_layers = layers;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Name::new("PrankPip"),
+                PipCamera,
+                Prank3dGizmoHidden,
+                UiCameraConfig { show_ui: false },
+                layers,
+                Camera3dBundle {
+                    camera: Camera {
+                        order: prank.order + 1,
+                        target: RenderTarget::Image(image.clone()),
+                        ..default()
+                    },
+                    camera_3d: Camera3d {
+                        clear_color,
+                        ..default()
+                    },
+                    transform,
+                    projection,
+                    ..default()
+                },
+            ));
+        }
+    }
+
+    let frame_viewport = viewport.clone();
+    let frame = Camera {
+        order: prank.order + 2,
+        target: prank.target.clone(),
+        viewport: Some(viewport),
+        hdr: prank.hdr,
+        ..default()
+    };
+    match pip_frame.get_single_mut() {
+        Ok((_, mut pip_frame)) => {
+            let viewport_changed = pip_frame.viewport.as_ref().is_none_or(|viewport| {
+                viewport.physical_position != frame_viewport.physical_position
+                    || viewport.physical_size != frame_viewport.physical_size
+            });
+            if pip_frame.order != frame.order
+                || pip_frame.hdr != frame.hdr
+                || pip_frame.target.normalize(None) != frame.target.normalize(None)
+                || viewport_changed
+            {
+                *pip_frame = frame;
+            }
+        }
+        Err(_) => {
+            let default_2d = Camera2dBundle::default();
+            commands
+                .spawn((
+                    Name::new("PrankPipFrame"),
+                    PipFrame,
+                    Prank3dGizmoHidden,
+                    UiCameraConfig { show_ui: false },
+                    RenderLayers::layer(FRAME_LAYER),
+                    Camera2dBundle {
+                        camera: frame,
+                        camera_2d: Camera2d {
+                            clear_color: ClearColorConfig::None,
+                        },
+                        projection: OrthographicProjection {
+                            scaling_mode: ScalingMode::Fixed {
+                                width: 1.0,
+                                height: 1.0,
+                            },
+                            ..default_2d.projection
+                        },
+                        ..default_2d
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        RenderLayers::layer(FRAME_LAYER),
+                        SpriteBundle {
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::ONE),
+                                ..default()
+                            },
+                            texture: image,
+                            transform: Transform::from_xyz(0.0, 0.0, -1.0),
+                            ..default()
+                        },
+                    ));
+                });
+        }
+    }
+}
+
+/// Moves gizmos to [`GIZMO_LAYER`] while the preview is shown, which only the active [`Prank3d`]
+/// is given, and moves them back once it is hidden.
+fn separate_gizmos(
+    mut commands: Commands,
+    mut pip: ResMut<Pip>,
+    mut gizmo_config: ResMut<GizmoConfig>,
+    active: Res<Prank3dActive>,
+    pranks: Query<(Entity, Option<&RenderLayers>), With<Prank3d>>,
+) {
+    let is_shown = pip.image.is_some();
+    match (is_shown, pip.gizmo_layers) {
+        (true, None) => {
+            pip.gizmo_layers = Some(gizmo_config.render_layers);
+            gizmo_config.render_layers = RenderLayers::layer(GIZMO_LAYER);
+        }
+        (false, Some(gizmo_layers)) => {
+            gizmo_config.render_layers = gizmo_layers;
+            pip.gizmo_layers = None;
+        }
+        _ => {}
+    }
+
+    for (entity, layers) in pranks.iter() {
+        let current = layers.copied().unwrap_or_default();
+        let layers = if is_shown && **active == Some(entity) {
+            current.with(GIZMO_LAYER)
+        } else {
+            current.without(GIZMO_LAYER)
+        };
+
+        if layers != current {
+            commands.entity(entity).insert(layers);
+        }
+    }
+}
+
+/// Creates an [`Image`] of `size` that a [`Camera`] is able to render into.
+fn offscreen_image(size: Extent3d) -> Image {
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    image
+}
+
+/// Computes the viewport of the preview in a corner of the viewport of `prank`.
+fn viewport(prank: &Camera, config: &Prank3dPipConfig, size: usize) -> Option<Viewport> {
+    let (min, max) = prank.physical_viewport_rect()?;
+    let prank_size = max - min;
+    let scalar = config.sizes.get(size).copied().unwrap_or(0.25);
+
+    let width = (scalar * prank_size.x as f32) as u32;
+    let height = width * prank_size.y / prank_size.x.max(1);
+    let physical_size = UVec2::new(width, height).max(UVec2::ONE);
+
+    let margin = UVec2::splat(config.margin);
+    let far = UVec2::new(
+        prank_size.x.saturating_sub(physical_size.x + margin.x),
+        prank_size.y.saturating_sub(physical_size.y + margin.y),
+    );
+    let offset = match config.corner {
+        Prank3dPipCorner::TopLeft => margin,
+        Prank3dPipCorner::TopRight => UVec2::new(far.x, margin.y),
+        Prank3dPipCorner::BottomLeft => UVec2::new(margin.x, far.y),
+        Prank3dPipCorner::BottomRight => far,
+    };
+
+    Some(Viewport {
+        physical_position: min + offset,
+        physical_size,
+        ..default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gizmos_move_to_active_prank_while_shown() {
+        let mut world = World::new();
+        world.init_resource::<Pip>();
+        world.init_resource::<GizmoConfig>();
+        let prank = world.spawn(Prank3d::default()).id();
+        let other = world.spawn(Prank3d::default()).id();
+        world.insert_resource(Prank3dActive(Some(prank)));
+        world.resource_mut::<Pip>().image = Some(Handle::default());
+
+        let mut separate = IntoSystem::into_system(separate_gizmos);
+        separate.initialize(&mut world);
+        separate.run((), &mut world);
+        separate.apply_deferred(&mut world);
+
+        assert_eq!(
+            world.resource::<GizmoConfig>().render_layers,
+            RenderLayers::layer(GIZMO_LAYER)
+        );
+        let layers = *world.get::<RenderLayers>(prank).expect("exists");
+        assert!(layers.intersects(&RenderLayers::layer(GIZMO_LAYER)));
+        assert!(layers.intersects(&RenderLayers::default()));
+        assert!(world.get::<RenderLayers>(other).is_none());
+
+        world.resource_mut::<Pip>().image = None;
+        separate.run((), &mut world);
+        separate.apply_deferred(&mut world);
+
+        assert_eq!(
+            world.resource::<GizmoConfig>().render_layers,
+            RenderLayers::default()
+        );
+        assert_eq!(
+            *world.get::<RenderLayers>(prank).expect("exists"),
+            RenderLayers::default()
+        );
+    }
+}
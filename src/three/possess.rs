@@ -0,0 +1,183 @@
+//! Provides possessing other cameras with, and ejecting from, three-dimensional cameras.
+
+use super::{
+    any_active, gizmo::Prank3dGizmoHidden, transition::Prank3dTransitionFinished, PendingLook,
+    Prank3d, Prank3dActive,
+};
+use crate::PrankConfig;
+use bevy::prelude::*;
+
+pub(super) struct Prank3dPossessPlugin;
+
+impl Plugin for Prank3dPossessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Possessed>()
+            .init_resource::<Ejected>()
+            .add_systems(Update, (possess.run_if(any_active), eject));
+    }
+}
+
+/// Three-dimensional camera possess and eject configuration.
+#[derive(Clone, Reflect)]
+pub struct Prank3dPossessConfig {
+    /// Key to teleport the active [`Prank3d`] to the pose and projection of the next [`Camera`]
+    /// with.
+    ///
+    /// Cycles through every [`Camera`] that has a camera gizmo.
+    pub possess_key: KeyCode,
+
+    /// Key to eject from the game [`Camera`] into a new [`Prank3d`] with.
+    ///
+    /// The [`Prank3d`] becomes active right away. The game [`Camera`] is disabled while
+    /// spectating, and pressing the key again despawns the [`Prank3d`] and restores it.
+    pub eject_key: KeyCode,
+}
+
+impl Default for Prank3dPossessConfig {
+    fn default() -> Self {
+        Self {
+            possess_key: KeyCode::G,
+            eject_key: KeyCode::F8,
+        }
+    }
+}
+
+/// The [`Camera`] that was last possessed.
+#[derive(Default, Resource)]
+struct Possessed(Option<Entity>);
+
+/// The [`Prank3d`] that was ejected into, and the game [`Camera`] that it was ejected from.
+#[derive(Default, Resource)]
+struct Ejected(Option<(Entity, Entity)>);
+
+fn possess(world: &mut World) {
+    let Some(config) = world.resource::<PrankConfig>().possess3d.as_ref() else {
+        return;
+    };
+    if !world
+        .resource::<Input<KeyCode>>()
+        .just_pressed(config.possess_key)
+    {
+        return;
+    }
+
+    let entity = world.resource::<Prank3dActive>().expect("is active");
+    let mut cameras = world
+        .query_filtered::<Entity, (With<Camera>, Without<Prank3dGizmoHidden>)>()
+        .iter(world)
+        .filter(|camera| *camera != entity)
+        .collect::<Vec<_>>();
+    cameras.sort();
+
+    let possessed = world.resource::<Possessed>().0;
+    let Some(target) = possessed
+        .and_then(|possessed| cameras.iter().copied().find(|camera| *camera > possessed))
+        .or_else(|| cameras.first().copied())
+    else {
+        return;
+    };
+    world.resource_mut::<Possessed>().0 = Some(target);
+
+    let target_ref = world.entity(target);
+    let transform = target_ref
+        .get::<GlobalTransform>()
+        .expect("exists")
+        .compute_transform();
+    let projection = target_ref.get::<Projection>().cloned();
+
+    let mut entity_mut = world.entity_mut(entity);
+    let mut prank = entity_mut.get_mut::<Prank3d>().expect("exists");
+    prank.translation = transform.translation;
+    let cancelled = prank.transition.take().is_some();
+    let mut prank_transform = entity_mut.get_mut::<Transform>().expect("exists");
+    prank_transform.translation = transform.translation;
+    prank_transform.rotation = transform.rotation;
+    if let (Some(projection), Some(mut prank_projection)) =
+        (projection, entity_mut.get_mut::<Projection>())
+    {
+        *prank_projection = projection;
+    }
+
+    world.resource_mut::<PendingLook>().0 = Vec2::ZERO;
+    if cancelled {
+        world.send_event(Prank3dTransitionFinished {
+            entity,
+            cancelled: true,
+        });
+    }
+}
+
+fn eject(
+    mut commands: Commands,
+    mut ejected: ResMut<Ejected>,
+    mut cameras: Query<
+        (Entity, &mut Camera, &GlobalTransform, Option<&Projection>),
+        (Without<Prank3d>, Without<Prank3dGizmoHidden>),
+    >,
+    pranks: Query<(), With<Prank3d>>,
+    config: Res<PrankConfig>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let Some(config) = config.possess3d.as_ref() else {
+        return;
+    };
+
+    if let Some((prank, camera)) = ejected.0 {
+        let is_despawned = pranks.get(prank).is_err();
+        if !is_despawned && !keyboard.just_pressed(config.eject_key) {
+            return;
+        }
+
+        if !is_despawned {
+            commands.entity(prank).despawn_recursive();
+        }
+        commands.add(move |world: &mut World| {
+            let mut active = world.resource_mut::<Prank3dActive>();
+            if **active == Some(prank) {
+                active.0 = None;
+            }
+        });
+        if let Ok((_, mut camera, ..)) = cameras.get_mut(camera) {
+            camera.is_active = true;
+        }
+        ejected.0 = None;
+        return;
+    }
+
+    if !keyboard.just_pressed(config.eject_key) {
+        return;
+    }
+
+    let Some((entity, mut camera, transform, projection)) = cameras
+        .iter_mut()
+        .filter(|(_, camera, ..)| camera.is_active)
+        .max_by_key(|(_, camera, ..)| camera.order)
+    else {
+        return;
+    };
+
+    let prank = commands
+        .spawn((
+            Name::new("PrankEject"),
+            Prank3d::default(),
+            Camera3dBundle {
+                camera: Camera {
+                    order: camera.order,
+                    target: camera.target.clone(),
+                    viewport: camera.viewport.clone(),
+                    ..default()
+                },
+                transform: transform.compute_transform(),
+                projection: projection.cloned().unwrap_or_default(),
+                ..default()
+            },
+        ))
+        .id();
+    // Activated once spawned, rather than whenever `sync_active` gets to it.
+    commands.add(move |world: &mut World| {
+        world.resource_mut::<Prank3dActive>().0 = Some(prank);
+    });
+
+    camera.is_active = false;
+    ejected.0 = Some((prank, entity));
+}
@@ -0,0 +1,850 @@
+//! Provides three-dimensional camera screenshots.
+
+use super::{any_active, gizmo::Prank3dGizmoHidden, Prank3dActive};
+use crate::PrankConfig;
+use bevy::{
+    core_pipeline::tonemapping::Tonemapping,
+    input::InputSystem,
+    prelude::*,
+    render::{
+        camera::{ExtractedCamera, NormalizedRenderTarget, RenderTarget},
+        render_asset::RenderAssets,
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d,
+            ImageCopyBuffer, ImageDataLayout, MapMode, TextureDescriptor, TextureDimension,
+            TextureFormat, TextureUsages,
+        },
+        renderer::{render_system, RenderDevice, RenderQueue},
+        texture::GpuImage,
+        view::screenshot::ScreenshotManager,
+        Render, RenderApp, RenderSet,
+    },
+    window::{PrimaryWindow, WindowRef},
+};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter},
+    mem,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub(super) struct Prank3dScreenshotPlugin;
+
+impl Plugin for Prank3dScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        let offscreen = OffscreenCaptures::default();
+
+        app.init_resource::<Prank3dScreenshotBackend>()
+            .init_resource::<Capturing>()
+            .insert_resource(offscreen.clone())
+            .add_systems(PreUpdate, request.after(InputSystem).run_if(any_active))
+            .add_systems(Update, despawn_offscreen)
+            .add_systems(Last, capture.run_if(capturing));
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(offscreen).add_systems(
+                Render,
+                read_back.after(render_system).in_set(RenderSet::Render),
+            );
+        }
+    }
+}
+
+/// Three-dimensional camera screenshot configuration.
+#[derive(Clone, Reflect)]
+pub struct Prank3dScreenshotConfig {
+    /// Key to take a screenshot of the active [`Prank3d`](super::Prank3d) with.
+    pub key: KeyCode,
+
+    /// Directory that screenshots are written to, created if missing.
+    pub directory: PathBuf,
+
+    /// Scalar of the resolution that screenshots are captured at.
+    ///
+    /// Only honored by [`Prank3dScreenshotSource`]s that are able to render offscreen, like
+    /// [`Prank3dOffscreenScreenshotSource`].
+    pub supersample: u32,
+
+    /// Whether to hide the HUD overlay and gizmos while capturing.
+    pub hide_overlays: bool,
+}
+
+impl Default for Prank3dScreenshotConfig {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::F12,
+            directory: PathBuf::from("screenshots"),
+            supersample: 1,
+            hide_overlays: true,
+        }
+    }
+}
+
+/// Source of the images that screenshots are written from.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::three::screenshot::{
+/// #     Prank3dScreenshotBackend, Prank3dScreenshotSource, Prank3dScreenshotTarget,
+/// # };
+/// #
+/// struct BlankSource;
+///
+/// impl Prank3dScreenshotSource for BlankSource {
+///     fn capture(
+///         &self,
+///         _world: &mut World,
+///         _camera: Entity,
+///         supersample: u32,
+///         target: Prank3dScreenshotTarget,
+///     ) {
+///         let size = 64 * supersample;
+///         let pixels = vec![255; (4 * size * size) as usize];
+///         target.save(size, size, &pixels);
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.insert_resource(Prank3dScreenshotBackend::new(BlankSource));
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+pub trait Prank3dScreenshotSource: Send + Sync + 'static {
+    /// Captures the view of `camera` at `supersample` times its resolution, and saves it to
+    /// `target` once it is available.
+    fn capture(
+        &self,
+        world: &mut World,
+        camera: Entity,
+        supersample: u32,
+        target: Prank3dScreenshotTarget,
+    );
+}
+
+/// The [`Prank3dScreenshotSource`] that is currently in use.
+///
+/// Defaults to [`Prank3dWindowScreenshotSource`].
+#[derive(Resource)]
+pub struct Prank3dScreenshotBackend(Box<dyn Prank3dScreenshotSource>);
+
+impl Prank3dScreenshotBackend {
+    /// Creates a new backend from `source`.
+    pub fn new(source: impl Prank3dScreenshotSource) -> Self {
+        Self(Box::new(source))
+    }
+}
+
+impl Deref for Prank3dScreenshotBackend {
+    type Target = dyn Prank3dScreenshotSource;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl Default for Prank3dScreenshotBackend {
+    fn default() -> Self {
+        Self::new(Prank3dWindowScreenshotSource)
+    }
+}
+
+/// Built-in [`Prank3dScreenshotSource`] that captures the window of the [`Camera`] and crops it
+/// to its viewport.
+///
+/// Since the window is captured as it is presented, screenshots with `supersample` greater than
+/// one are delegated to [`Prank3dOffscreenScreenshotSource`].
+#[derive(Default)]
+pub struct Prank3dWindowScreenshotSource;
+
+impl Prank3dScreenshotSource for Prank3dWindowScreenshotSource {
+    fn capture(
+        &self,
+        world: &mut World,
+        camera: Entity,
+        supersample: u32,
+        target: Prank3dScreenshotTarget,
+    ) {
+        if supersample > 1 {
+            Prank3dOffscreenScreenshotSource.capture(world, camera, supersample, target);
+            return;
+        }
+
+        let Some(camera) = world.get::<Camera>(camera) else {
+            return;
+        };
+        let viewport = camera.physical_viewport_rect();
+        let RenderTarget::Window(winref) = camera.target else {
+            warn!("screenshots are only supported for cameras rendering to a window");
+            return;
+        };
+        let window = match winref {
+            WindowRef::Primary => world
+                .query_filtered::<Entity, With<PrimaryWindow>>()
+                .get_single(world)
+                .ok(),
+            WindowRef::Entity(entity) => Some(entity),
+        };
+        let Some(window) = window else {
+            return;
+        };
+
+        let result =
+            world
+                .resource_mut::<ScreenshotManager>()
+                .take_screenshot(window, move |image| {
+                    let image = match image.try_into_dynamic() {
+                        Ok(image) => image.to_rgba8(),
+                        Err(err) => {
+                            error!("failed to convert screenshot: {:?}", err);
+                            return;
+                        }
+                    };
+                    let size = UVec2::new(image.width(), image.height());
+                    let (min, max) = viewport.unwrap_or((UVec2::ZERO, size));
+                    let (min, max) = (min.min(size), max.min(size));
+
+                    let mut pixels = crop(image.as_raw(), size.x, min, max);
+                    make_opaque(&mut pixels);
+                    target.save(max.x - min.x, max.y - min.y, &pixels);
+                });
+        if let Err(err) = result {
+            warn!("failed to take screenshot: {}", err);
+        }
+    }
+}
+
+/// Built-in [`Prank3dScreenshotSource`] that renders the view of the [`Camera`] into an offscreen
+/// [`Image`] at `supersample` times the size of its viewport, and reads it back.
+///
+/// The HUD overlay is never rendered into the [`Image`], and gizmos stay hidden until it is read
+/// back if `hide_overlays` field of [`Prank3dScreenshotConfig`] is enabled.
+#[derive(Default)]
+pub struct Prank3dOffscreenScreenshotSource;
+
+impl Prank3dScreenshotSource for Prank3dOffscreenScreenshotSource {
+    fn capture(
+        &self,
+        world: &mut World,
+        camera: Entity,
+        supersample: u32,
+        target: Prank3dScreenshotTarget,
+    ) {
+        let Some(entity_ref) = world.get_entity(camera) else {
+            return;
+        };
+        let Some(camera) = entity_ref.get::<Camera>() else {
+            return;
+        };
+        let Some(size) = camera.physical_viewport_size() else {
+            return;
+        };
+        if size.x == 0 || size.y == 0 {
+            return;
+        }
+
+        let hdr = camera.hdr;
+        let camera_3d = entity_ref.get::<Camera3d>().cloned().unwrap_or_default();
+        let tonemapping = entity_ref.get::<Tonemapping>().cloned().unwrap_or_default();
+        let global_transform = entity_ref
+            .get::<GlobalTransform>()
+            .copied()
+            .unwrap_or_default();
+        let projection = entity_ref.get::<Projection>().cloned().unwrap_or_default();
+
+        let size = Extent3d {
+            width: supersample * size.x,
+            height: supersample * size.y,
+            depth_or_array_layers: 1,
+        };
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: None,
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8UnormSrgb,
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..default()
+        };
+        image.resize(size);
+        let image = world.resource_mut::<Assets<Image>>().add(image);
+
+        world.spawn((
+            Name::new("PrankScreenshot"),
+            OffscreenCamera(image.clone_weak()),
+            Prank3dGizmoHidden,
+            UiCameraConfig { show_ui: false },
+            Camera3dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image.clone()),
+                    hdr,
+                    ..default()
+                },
+                camera_3d,
+                tonemapping,
+                transform: global_transform.compute_transform(),
+                global_transform,
+                projection,
+                ..default()
+            },
+        ));
+
+        let hide_overlays = world
+            .resource::<PrankConfig>()
+            .screenshot3d
+            .as_ref()
+            .is_some_and(|config| config.hide_overlays);
+        world
+            .resource::<OffscreenCaptures>()
+            .lock()
+            .pending
+            .push(OffscreenCapture {
+                image: image.clone_weak(),
+                hide_overlays,
+                target,
+            });
+    }
+}
+
+/// Destination of a screenshot, along with the pose of the [`Prank3d`](super::Prank3d) it was
+/// taken from.
+pub struct Prank3dScreenshotTarget {
+    path: PathBuf,
+    metadata: Vec<(String, String)>,
+}
+
+impl Prank3dScreenshotTarget {
+    /// Creates a new target that writes to `path`, embedding `metadata` as keyword and text pairs.
+    pub fn new(path: impl Into<PathBuf>, metadata: Vec<(String, String)>) -> Self {
+        Self {
+            path: path.into(),
+            metadata,
+        }
+    }
+
+    /// Path of the PNG file that the screenshot is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keyword and text pairs that are embedded into the PNG file.
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Writes `pixels`, rows of tightly packed 8-bit RGBA of `width` and `height`, as a PNG file
+    /// with the pose embedded as text chunks.
+    pub fn save(self, width: u32, height: u32, pixels: &[u8]) {
+        match write_png(&self.path, width, height, pixels, &self.metadata) {
+            Ok(()) => info!("saved screenshot to {}", self.path.display()),
+            Err(err) => error!(
+                "failed to save screenshot to {}: {}",
+                self.path.display(),
+                err
+            ),
+        }
+    }
+}
+
+/// Whether a screenshot is captured in the current frame.
+#[derive(Default, Resource)]
+pub(super) struct Capturing {
+    requested: bool,
+
+    /// Whether overlays are hidden in the current frame, either for a screenshot that is captured
+    /// in it or for an offscreen screenshot that is yet to be rendered.
+    ///
+    /// Decided in [`PreUpdate`], before any overlay is drawn.
+    pub(super) hide_overlays: bool,
+}
+
+pub(super) fn overlays_shown(capturing: Res<Capturing>) -> bool {
+    !capturing.hide_overlays
+}
+
+fn capturing(capturing: Res<Capturing>) -> bool {
+    capturing.requested
+}
+
+/// Screenshots of [`Prank3dOffscreenScreenshotSource`] that are shared with the render world.
+#[derive(Clone, Default, Resource)]
+pub(super) struct OffscreenCaptures(Arc<Mutex<OffscreenQueue>>);
+
+impl OffscreenCaptures {
+    fn lock(&self) -> MutexGuard<'_, OffscreenQueue> {
+        self.0.lock().expect("not poisoned")
+    }
+}
+
+#[derive(Default)]
+struct OffscreenQueue {
+    /// Screenshots whose [`Image`] is yet to be rendered.
+    pending: Vec<OffscreenCapture>,
+
+    /// [`Image`]s that have been copied, whose [`Camera`]s can be despawned.
+    finished: Vec<Handle<Image>>,
+}
+
+struct OffscreenCapture {
+    image: Handle<Image>,
+    hide_overlays: bool,
+    target: Prank3dScreenshotTarget,
+}
+
+/// The [`Camera`] that renders into the [`Image`] of an offscreen screenshot.
+#[derive(Component)]
+struct OffscreenCamera(Handle<Image>);
+
+/// Buffer that an offscreen screenshot is being read back into.
+struct Readback {
+    buffer: Buffer,
+    mapped: Arc<Mutex<Option<bool>>>,
+    width: u32,
+    height: u32,
+    target: Prank3dScreenshotTarget,
+}
+
+impl Readback {
+    /// Copies `image` into a new buffer and starts mapping it.
+    fn new(
+        image: &GpuImage,
+        target: Prank3dScreenshotTarget,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+    ) -> Self {
+        let width = image.size.x as u32;
+        let height = image.size.y as u32;
+        let padded_row = RenderDevice::align_copy_bytes_per_row(4 * width as usize);
+
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("prank_screenshot_buffer"),
+            size: (padded_row * height as usize) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("prank_screenshot_encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            image.texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row as u32),
+                    rows_per_image: None,
+                },
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        render_queue.submit([encoder.finish()]);
+
+        let mapped = Arc::new(Mutex::new(None));
+        let callback_mapped = mapped.clone();
+        buffer.slice(..).map_async(MapMode::Read, move |result| {
+            if let Err(err) = &result {
+                error!("failed to read back screenshot: {}", err);
+            }
+            *callback_mapped.lock().expect("not poisoned") = Some(result.is_ok());
+        });
+
+        Self {
+            buffer,
+            mapped,
+            width,
+            height,
+            target,
+        }
+    }
+
+    /// Whether mapping has finished, either successfully or not.
+    fn is_done(&self) -> bool {
+        self.mapped.lock().expect("not poisoned").is_some()
+    }
+
+    /// Saves the mapped buffer to the target, if mapping succeeded.
+    fn save(self) {
+        if *self.mapped.lock().expect("not poisoned") != Some(true) {
+            return;
+        }
+
+        let row = 4 * self.width as usize;
+        let padded_row = RenderDevice::align_copy_bytes_per_row(row);
+        let mut pixels = {
+            let data = self.buffer.slice(..).get_mapped_range();
+            data.chunks(padded_row)
+                .flat_map(|padded| padded[..row].iter().copied())
+                .collect::<Vec<_>>()
+        };
+        self.buffer.unmap();
+
+        make_opaque(&mut pixels);
+        self.target.save(self.width, self.height, &pixels);
+    }
+}
+
+fn despawn_offscreen(
+    mut commands: Commands,
+    offscreen: Res<OffscreenCaptures>,
+    cameras: Query<(Entity, &OffscreenCamera)>,
+) {
+    let finished = mem::take(&mut offscreen.lock().finished);
+    if finished.is_empty() {
+        return;
+    }
+
+    for (entity, camera) in cameras.iter() {
+        if finished.contains(&camera.0) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Copies offscreen screenshots that have been rendered in the current frame into buffers, and
+/// saves them once they are mapped.
+///
+/// Runs in the render world. A [`Camera`] is only extracted once its target has been resolved, so
+/// an [`Image`] is not rendered before its [`ExtractedCamera`] shows up.
+fn read_back(
+    mut readbacks: Local<Vec<Readback>>,
+    offscreen: Res<OffscreenCaptures>,
+    cameras: Query<&ExtractedCamera>,
+    images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let mut queue = offscreen.lock();
+    for capture in mem::take(&mut queue.pending) {
+        let target = NormalizedRenderTarget::Image(capture.image.clone_weak());
+        let rendered = cameras
+            .iter()
+            .any(|camera| camera.target.as_ref() == Some(&target));
+        let Some(image) = images.get(&capture.image).filter(|_| rendered) else {
+            queue.pending.push(capture);
+            continue;
+        };
+
+        readbacks.push(Readback::new(
+            image,
+            capture.target,
+            &render_device,
+            &render_queue,
+        ));
+        queue.finished.push(capture.image);
+    }
+    drop(queue);
+
+    let (done, mapping) = mem::take(&mut *readbacks)
+        .into_iter()
+        .partition::<Vec<_>, _>(Readback::is_done);
+    *readbacks = mapping;
+    for readback in done {
+        readback.save();
+    }
+}
+
+fn request(
+    mut capturing: ResMut<Capturing>,
+    offscreen: Res<OffscreenCaptures>,
+    config: Res<PrankConfig>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let rendering = offscreen
+        .lock()
+        .pending
+        .iter()
+        .any(|capture| capture.hide_overlays);
+    let hide_overlays = match config.screenshot3d.as_ref() {
+        Some(config) if keyboard.just_pressed(config.key) => {
+            capturing.requested = true;
+            config.hide_overlays || rendering
+        }
+        _ => rendering,
+    };
+
+    if capturing.hide_overlays != hide_overlays {
+        capturing.hide_overlays = hide_overlays;
+    }
+}
+
+fn capture(world: &mut World) {
+    world.resource_mut::<Capturing>().requested = false;
+    let Some(config) = world.resource::<PrankConfig>().screenshot3d.clone() else {
+        return;
+    };
+    let Some(entity) = **world.resource::<Prank3dActive>() else {
+        return;
+    };
+    let Some(transform) = world.get::<Transform>(entity) else {
+        return;
+    };
+
+    if let Err(err) = fs::create_dir_all(&config.directory) {
+        error!(
+            "failed to create screenshot directory {}: {}",
+            config.directory.display(),
+            err,
+        );
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let metadata = pose_metadata(transform, world.get::<Projection>(entity));
+    let target = Prank3dScreenshotTarget::new(
+        config.directory.join(format!("prank-{}.png", timestamp)),
+        metadata,
+    );
+
+    world.resource_scope(|world, backend: Mut<Prank3dScreenshotBackend>| {
+        backend.capture(world, entity, config.supersample.max(1), target);
+    });
+}
+
+/// Describes the pose of `transform` and `projection` as keyword and text pairs.
+fn pose_metadata(transform: &Transform, projection: Option<&Projection>) -> Vec<(String, String)> {
+    let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    let mut metadata = vec![
+        (
+            "Translation".to_string(),
+            format!("{:?}", transform.translation),
+        ),
+        ("Rotation".to_string(), format!("{:?}", transform.rotation)),
+        (
+            "Euler".to_string(),
+            format!(
+                "yaw {:.2} pitch {:.2} roll {:.2}",
+                yaw.to_degrees(),
+                pitch.to_degrees(),
+                roll.to_degrees(),
+            ),
+        ),
+    ];
+    match projection {
+        Some(Projection::Perspective(projection)) => {
+            metadata.push(("Fov".to_string(), projection.fov.to_degrees().to_string()));
+        }
+        Some(Projection::Orthographic(projection)) => {
+            metadata.push(("Scale".to_string(), projection.scale.to_string()));
+        }
+        None => {}
+    }
+
+    metadata
+}
+
+/// Crops `pixels`, rows of tightly packed 8-bit RGBA of `width`, to the rectangle from `min` to
+/// `max`.
+fn crop(pixels: &[u8], width: u32, min: UVec2, max: UVec2) -> Vec<u8> {
+    (min.y..max.y)
+        .flat_map(|y| {
+            let start = 4 * (y * width + min.x) as usize;
+            let end = 4 * (y * width + max.x) as usize;
+            pixels[start..end].iter().copied()
+        })
+        .collect()
+}
+
+/// Sets the alpha of `pixels`, tightly packed 8-bit RGBA, to fully opaque.
+///
+/// The alpha channel of the swapchain is not meaningful, and holds brightness data with HDR.
+fn make_opaque(pixels: &mut [u8]) {
+    for alpha in pixels.iter_mut().skip(3).step_by(4) {
+        *alpha = u8::MAX;
+    }
+}
+
+fn write_png(
+    path: &Path,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    metadata: &[(String, String)],
+) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    for (keyword, text) in metadata.iter() {
+        encoder.add_text_chunk(keyword.clone(), text.clone())?;
+    }
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Source that captures a gradient of 3 by 2 pixels at `supersample`.
+    struct MockSource;
+
+    impl Prank3dScreenshotSource for MockSource {
+        fn capture(
+            &self,
+            _world: &mut World,
+            _camera: Entity,
+            supersample: u32,
+            target: Prank3dScreenshotTarget,
+        ) {
+            let (width, height) = (3 * supersample, 2 * supersample);
+            target.save(width, height, &gradient(width, height));
+        }
+    }
+
+    fn gradient(width: u32, height: u32) -> Vec<u8> {
+        (0..4 * width * height).map(|byte| byte as u8).collect()
+    }
+
+    #[test]
+    fn capture_writes_png_with_pose() {
+        let directory =
+            std::env::temp_dir().join(format!("bevy_prank_screenshot_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let fov = 1.2_f32;
+
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                transform,
+                Projection::Perspective(PerspectiveProjection { fov, ..default() }),
+            ))
+            .id();
+        world.insert_resource(Prank3dActive(Some(entity)));
+        world.insert_resource(PrankConfig {
+            screenshot3d: Some(Prank3dScreenshotConfig {
+                directory: directory.clone(),
+                supersample: 2,
+                ..default()
+            }),
+            ..default()
+        });
+        world.insert_resource(Capturing::default());
+        world.insert_resource(Prank3dScreenshotBackend::new(MockSource));
+
+        capture(&mut world);
+
+        let paths = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(paths.len(), 1);
+
+        let mut reader = png::Decoder::new(File::open(&paths[0]).unwrap())
+            .read_info()
+            .unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        assert_eq!(pixels[..info.buffer_size()], gradient(6, 4));
+
+        let text = reader
+            .info()
+            .uncompressed_latin1_text
+            .iter()
+            .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(text["Translation"], format!("{:?}", transform.translation));
+        assert_eq!(text["Rotation"], format!("{:?}", transform.rotation));
+        assert_eq!(text["Fov"], fov.to_degrees().to_string());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn pose_metadata_describes_orthographic_scale() {
+        let metadata = pose_metadata(
+            &Transform::default(),
+            Some(&Projection::Orthographic(OrthographicProjection {
+                scale: 2.5,
+                ..default()
+            })),
+        );
+
+        assert!(metadata.contains(&("Scale".to_string(), "2.5".to_string())));
+        assert!(!metadata.iter().any(|(keyword, _)| keyword == "Fov"));
+    }
+
+    #[test]
+    fn crop_keeps_rectangle() {
+        let pixels = (0..6).flat_map(|pixel| [pixel; 4]).collect::<Vec<u8>>();
+
+        let cropped = crop(&pixels, 3, UVec2::new(1, 0), UVec2::new(3, 2));
+        let expected = [1, 2, 4, 5]
+            .into_iter()
+            .flat_map(|pixel| [pixel; 4])
+            .collect::<Vec<u8>>();
+        assert_eq!(cropped, expected);
+    }
+
+    #[test]
+    fn crop_keeps_everything() {
+        let pixels = gradient(3, 2);
+
+        assert_eq!(crop(&pixels, 3, UVec2::ZERO, UVec2::new(3, 2)), pixels);
+    }
+
+    #[test]
+    fn crop_empty_rectangle() {
+        let pixels = gradient(3, 2);
+
+        assert!(crop(&pixels, 3, UVec2::ONE, UVec2::ONE).is_empty());
+    }
+
+    #[test]
+    fn make_opaque_only_sets_alpha() {
+        let mut pixels = vec![10, 20, 30, 0, 40, 50, 60, 128];
+        make_opaque(&mut pixels);
+
+        assert_eq!(pixels, [10, 20, 30, 255, 40, 50, 60, 255]);
+    }
+
+    #[test]
+    fn overlays_stay_hidden_until_offscreen_capture_is_rendered() {
+        let mut world = World::new();
+        world.insert_resource(PrankConfig::default());
+        world.insert_resource(Input::<KeyCode>::default());
+        world.insert_resource(Capturing::default());
+        let offscreen = OffscreenCaptures::default();
+        offscreen.lock().pending.push(OffscreenCapture {
+            image: Handle::default(),
+            hide_overlays: true,
+            target: Prank3dScreenshotTarget::new("unused.png", Vec::new()),
+        });
+        world.insert_resource(offscreen.clone());
+
+        let mut request = IntoSystem::into_system(request);
+        request.initialize(&mut world);
+
+        request.run((), &mut world);
+        assert!(world.resource::<Capturing>().hide_overlays);
+
+        offscreen.lock().pending.clear();
+        request.run((), &mut world);
+        assert!(!world.resource::<Capturing>().hide_overlays);
+    }
+}
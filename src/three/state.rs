@@ -34,12 +34,13 @@ impl Plugin for Prank3dStatePlugin {
 pub(super) enum Prank3dState {
     Fly,
     Offset,
+    Walk,
     #[default]
     None,
 }
 
 #[derive(Default, Resource)]
-pub(super) struct Prank3dActive(Option<Entity>);
+pub(super) struct Prank3dActive(pub(super) Option<Entity>);
 
 impl Deref for Prank3dActive {
     type Target = Option<Entity>;
@@ -69,21 +70,25 @@ fn sync_active(
         return;
     };
 
-    let active_entity = pranks
-        .iter()
-        .find(|(_, camera, prank)| {
-            if !prank.is_active {
-                return false;
-            }
-            let Some(NormalizedRenderTarget::Window(winref)) = camera
-                .target
-                .normalize(primary_window.map(|(entity, _)| entity))
-            else {
-                return false;
-            };
-
-            winref.entity() == focused_window
-        })
+    let is_focused = |(_, camera, prank): &(Entity, &Camera, &Prank3d)| {
+        if !prank.is_active {
+            return false;
+        }
+        let Some(NormalizedRenderTarget::Window(winref)) = camera
+            .target
+            .normalize(primary_window.map(|(entity, _)| entity))
+        else {
+            return false;
+        };
+
+        winref.entity() == focused_window
+    };
+
+    // Keep the current one while it is focused, so that one set explicitly is not overridden.
+    let active_entity = active
+        .and_then(|entity| pranks.get(entity).ok())
+        .filter(is_focused)
+        .or_else(|| pranks.iter().find(is_focused))
         .map(|(entity, _, _)| entity);
 
     if active_entity != **active {
@@ -93,14 +98,16 @@ fn sync_active(
 
 fn sync_state(
     active: Res<Prank3dActive>,
+    pranks: Query<&Prank3d>,
     prev_state: Res<State<Prank3dState>>,
     mut state: ResMut<NextState<Prank3dState>>,
     mouse: Res<Input<MouseButton>>,
+    keyboard: Res<Input<KeyCode>>,
 ) {
-    if active.is_none() {
+    let Some(prank) = active.and_then(|entity| pranks.get(entity).ok()) else {
         state.set(Prank3dState::None);
         return;
-    }
+    };
 
     match **prev_state {
         Prank3dState::Fly => {
@@ -113,8 +120,15 @@ fn sync_state(
                 state.set(Prank3dState::None);
             }
         }
+        Prank3dState::Walk => {
+            if keyboard.just_pressed(prank.walk_key) {
+                state.set(Prank3dState::None);
+            }
+        }
         Prank3dState::None => {
-            if mouse.pressed(MouseButton::Right) {
+            if keyboard.just_pressed(prank.walk_key) {
+                state.set(Prank3dState::Walk);
+            } else if mouse.pressed(MouseButton::Right) {
                 state.set(Prank3dState::Fly);
             } else if mouse.pressed(MouseButton::Middle) {
                 state.set(Prank3dState::Offset);
@@ -150,6 +164,10 @@ fn sync_cursor(
             window.cursor.visible = false;
             window.cursor.grab_mode = CursorGrabMode::Locked;
         }
+        Prank3dState::Walk => {
+            window.cursor.visible = false;
+            window.cursor.grab_mode = CursorGrabMode::Locked;
+        }
         Prank3dState::None => {
             window.cursor.visible = true;
             window.cursor.grab_mode = CursorGrabMode::None;
@@ -0,0 +1,219 @@
+//! Provides switching between cameras.
+
+use super::{
+    hud::{append_segment, register_segment, Prank3dHudSegment},
+    transition::{Prank3dEasing, Prank3dPose},
+    Prank3d,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+
+pub(super) struct Prank3dSwitcherPlugin;
+
+impl Plugin for Prank3dSwitcherPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, switch.run_if(resource_exists::<Prank3dSwitcher>()));
+
+        register_segment(&mut app.world, Prank3dHudSegment::Switcher, segment);
+        append_segment(&mut app.world, Prank3dHudSegment::Switcher);
+    }
+}
+
+/// Switches between cameras, activating one at a time among those that render to the same
+/// target.
+///
+/// [`Prank3d`] cameras are activated for input along with their [`Camera`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     let game_camera = commands.spawn(Camera3dBundle::default()).id();
+///     let prank = commands
+///         .spawn((Prank3d::default(), Camera3dBundle::default()))
+///         .id();
+///
+///     commands.insert_resource(Prank3dSwitcher::new(vec![game_camera, prank]));
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Resource)]
+pub struct Prank3dSwitcher {
+    /// Cameras to switch between in order.
+    pub cameras: Vec<Entity>,
+
+    /// Key to switch to the next camera with.
+    pub cycle_key: KeyCode,
+
+    /// Whether number keys switch to the camera of that number, starting from one.
+    pub number_keys: bool,
+
+    /// Seconds that a [`Prank3d`] takes to blend from the pose of the previous camera to its own
+    /// when switched to.
+    ///
+    /// Zero disables blending.
+    pub transition_duration: f32,
+
+    /// Easing function of the blend.
+    pub transition_easing: Prank3dEasing,
+
+    current: usize,
+}
+
+impl Prank3dSwitcher {
+    /// Creates a new switcher between `cameras`, starting with the first one.
+    pub fn new(cameras: Vec<Entity>) -> Self {
+        Self {
+            cameras,
+            cycle_key: KeyCode::Tab,
+            number_keys: true,
+            transition_duration: 0.0,
+            transition_easing: Prank3dEasing::SmoothStep,
+            current: 0,
+        }
+    }
+
+    /// The camera that is currently switched to.
+    pub fn current(&self) -> Option<Entity> {
+        self.cameras.get(self.current).copied()
+    }
+}
+
+const NUMBER_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+fn switch(
+    mut switcher: ResMut<Prank3dSwitcher>,
+    mut cameras: Query<(&mut Camera, &GlobalTransform, Option<&mut Prank3d>)>,
+    mut poses: Query<(&mut Transform, Option<&mut Projection>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let previous = switcher.current;
+    let len = switcher.cameras.len();
+    if len == 0 {
+        return;
+    }
+
+    let next = if keyboard.just_pressed(switcher.cycle_key) {
+        (previous + 1) % len
+    } else if let Some(index) = NUMBER_KEYS
+        .iter()
+        .position(|key| switcher.number_keys && keyboard.just_pressed(*key))
+        .filter(|index| *index < len)
+    {
+        index
+    } else if switcher.is_added() {
+        previous
+    } else {
+        return;
+    };
+
+    let primary_window = primary_window.get_single().ok();
+    let previous_entity = switcher.cameras[previous];
+    let previous_pose = cameras.get(previous_entity).ok().map(|(_, transform, _)| {
+        let transform = transform.compute_transform();
+        Prank3dPose {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            projection: poses
+                .get(previous_entity)
+                .ok()
+                .and_then(|(_, projection)| projection.cloned()),
+        }
+    });
+    let next_entity = switcher.cameras[next];
+    let Ok((next_camera, ..)) = cameras.get(next_entity) else {
+        return;
+    };
+    let next_target = next_camera.target.normalize(primary_window);
+    switcher.current = next;
+
+    for entity in switcher.cameras.iter().copied() {
+        let Ok((mut camera, _, prank)) = cameras.get_mut(entity) else {
+            continue;
+        };
+        if camera.target.normalize(primary_window) != next_target {
+            continue;
+        }
+
+        let is_active = entity == next_entity;
+        if camera.is_active != is_active {
+            camera.is_active = is_active;
+        }
+        if let Some(mut prank) = prank {
+            if prank.is_active != is_active {
+                prank.is_active = is_active;
+            }
+        }
+    }
+
+    if switcher.transition_duration <= 0.0 || next == previous {
+        return;
+    }
+    let (Some(from), Ok((_, _, Some(mut prank))), Ok((mut transform, projection))) = (
+        previous_pose,
+        cameras.get_mut(next_entity),
+        poses.get_mut(next_entity),
+    ) else {
+        return;
+    };
+
+    let target = Prank3dPose {
+        translation: prank.translation,
+        rotation: transform.rotation,
+        projection: projection.as_deref().cloned(),
+    };
+    transform.translation = from.translation;
+    transform.rotation = from.rotation;
+    if let (Some(mut projection), Some(from)) = (projection, from.projection) {
+        *projection = from;
+    }
+
+    prank.translation = from.translation;
+    prank.start_transition(
+        target,
+        switcher.transition_duration,
+        switcher.transition_easing,
+    );
+}
+
+fn segment(
+    In(_): In<Entity>,
+    switcher: Option<Res<Prank3dSwitcher>>,
+    names: Query<&Name>,
+) -> String {
+    let Some(switcher) = switcher else {
+        return String::new();
+    };
+
+    switcher
+        .cameras
+        .iter()
+        .enumerate()
+        .map(|(index, entity)| {
+            let name = names
+                .get(*entity)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|_| format!("{:?}", entity));
+            if index == switcher.current {
+                format!("[{}: {}]", index + 1, name)
+            } else {
+                format!("{}: {}", index + 1, name)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
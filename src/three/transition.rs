@@ -0,0 +1,409 @@
+//! Provides blending three-dimensional cameras between poses.
+
+use super::{interpolation, walk::is_moving, Prank3d, Prank3dActive, Prank3dState};
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+pub(super) struct Prank3dTransitionPlugin;
+
+impl Plugin for Prank3dTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Prank3dEasing>()
+            .add_event::<Prank3dTransitionFinished>()
+            .add_systems(Update, transition.after(interpolation));
+    }
+}
+
+/// Pose that a [`Prank3d`] transitions to.
+#[derive(Clone, Reflect)]
+pub struct Prank3dPose {
+    /// World space translation.
+    pub translation: Vec3,
+
+    /// World space rotation.
+    pub rotation: Quat,
+
+    /// Projection to blend the field of view or scale to.
+    ///
+    /// `None` keeps the current [`Projection`].
+    pub projection: Option<Projection>,
+}
+
+impl From<Transform> for Prank3dPose {
+    fn from(transform: Transform) -> Self {
+        Self {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            projection: None,
+        }
+    }
+}
+
+/// Easing function that maps the progress of a transition to its blend factor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum Prank3dEasing {
+    /// Constant speed.
+    Linear,
+
+    /// Quadratic acceleration.
+    QuadraticIn,
+
+    /// Quadratic deceleration.
+    QuadraticOut,
+
+    /// Quadratic acceleration until halfway, then deceleration.
+    QuadraticInOut,
+
+    /// Cubic acceleration.
+    CubicIn,
+
+    /// Cubic deceleration.
+    CubicOut,
+
+    /// Cubic acceleration until halfway, then deceleration.
+    CubicInOut,
+
+    /// Hermite interpolation with zero speed at both ends.
+    #[default]
+    SmoothStep,
+}
+
+impl Prank3dEasing {
+    /// Returns the blend factor at `t`, clamped to range `[0.0, 1.0]`.
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Self::Linear => t,
+            Self::QuadraticIn => t * t,
+            Self::QuadraticOut => t * (2.0 - t),
+            Self::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t).powi(2)
+                }
+            }
+            Self::CubicIn => t.powi(3),
+            Self::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t.powi(3)
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Self::SmoothStep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Transition of a [`Prank3d`] that is in progress.
+///
+/// See [`Prank3d::start_transition`] for how to start one.
+#[derive(Clone, Reflect)]
+pub struct Prank3dTransition {
+    from: Option<Prank3dPose>,
+    to: Prank3dPose,
+    duration: f32,
+    easing: Prank3dEasing,
+    elapsed: f32,
+}
+
+impl Prank3dTransition {
+    /// The pose that is transitioned to.
+    pub fn target(&self) -> &Prank3dPose {
+        &self.to
+    }
+
+    /// Progress of the transition in range `[0.0, 1.0]`.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Prank3d {
+    /// Starts blending from the current pose to `target` over `duration` seconds with `easing`.
+    ///
+    /// Replaces the transition in progress, if any. The transition is cancelled once the user
+    /// starts controlling the [`Camera`], and [`Prank3dTransitionFinished`] is sent either way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prank::prelude::*;
+    /// #
+    /// fn look_at_origin(mut pranks: Query<&mut Prank3d>, keyboard: Res<Input<KeyCode>>) {
+    ///     if !keyboard.just_pressed(KeyCode::O) {
+    ///         return;
+    ///     }
+    ///
+    ///     for mut prank in pranks.iter_mut() {
+    ///         prank.start_transition(
+    ///             Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ///             1.0,
+    ///             Prank3dEasing::CubicInOut,
+    ///         );
+    ///     }
+    /// }
+    /// #
+    /// # bevy::ecs::system::assert_is_system(look_at_origin);
+    /// ```
+    pub fn start_transition(
+        &mut self,
+        target: impl Into<Prank3dPose>,
+        duration: f32,
+        easing: Prank3dEasing,
+    ) {
+        self.transition = Some(Prank3dTransition {
+            from: None,
+            to: target.into(),
+            duration: duration.max(0.0),
+            easing,
+            elapsed: 0.0,
+        });
+    }
+}
+
+/// Sent when a transition of a [`Prank3d`] finishes.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prank::prelude::*;
+/// #
+/// fn log_finished(mut finished: EventReader<Prank3dTransitionFinished>) {
+///     for finished in finished.iter() {
+///         if finished.cancelled {
+///             info!("transition of {:?} was cancelled", finished.entity);
+///         }
+///     }
+/// }
+/// #
+/// # bevy::ecs::system::assert_is_system(log_finished);
+/// ```
+#[derive(Clone, Copy, Debug, Event)]
+pub struct Prank3dTransitionFinished {
+    /// The [`Prank3d`] that transitioned.
+    pub entity: Entity,
+
+    /// Whether the transition was cancelled before reaching its target.
+    pub cancelled: bool,
+}
+
+fn transition(
+    mut pranks: Query<(
+        Entity,
+        &mut Transform,
+        Option<&mut Projection>,
+        &mut Prank3d,
+    )>,
+    mut finished: EventWriter<Prank3dTransitionFinished>,
+    active: Res<Prank3dActive>,
+    state: Res<State<Prank3dState>>,
+    time: Res<Time>,
+    mut motion: EventReader<MouseMotion>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    // Walking only takes control once the user looks or moves around.
+    let is_controlled = match **state {
+        Prank3dState::None => false,
+        Prank3dState::Walk => {
+            motion.iter().any(|motion| motion.delta != Vec2::ZERO) || is_moving(&keyboard)
+        }
+        Prank3dState::Fly | Prank3dState::Offset => true,
+    };
+
+    for (entity, mut transform, mut projection, mut prank) in pranks.iter_mut() {
+        let Some(transition) = prank.transition.as_mut() else {
+            continue;
+        };
+
+        if **active == Some(entity) && is_controlled {
+            prank.transition = None;
+            finished.send(Prank3dTransitionFinished {
+                entity,
+                cancelled: true,
+            });
+            continue;
+        }
+
+        transition.elapsed += time.delta_seconds();
+        let t = transition.easing.ease(transition.progress());
+        let is_finished = transition.progress() >= 1.0;
+
+        let from = transition.from.get_or_insert_with(|| Prank3dPose {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            projection: projection.as_deref().cloned(),
+        });
+
+        transform.translation = from.translation.lerp(transition.to.translation, t);
+        transform.rotation = from.rotation.slerp(transition.to.rotation, t);
+        if let (Some(projection), Some(from), Some(to)) = (
+            projection.as_mut(),
+            from.projection.as_ref(),
+            transition.to.projection.as_ref(),
+        ) {
+            **projection = blend_projection(from, to, t);
+        }
+
+        prank.translation = transform.translation;
+        if is_finished {
+            prank.transition = None;
+            finished.send(Prank3dTransitionFinished {
+                entity,
+                cancelled: false,
+            });
+        }
+    }
+}
+
+/// Blends the field of view or scale of `from` towards `to` by `t`.
+///
+/// Projections of different kinds switch once `t` reaches `1.0`.
+fn blend_projection(from: &Projection, to: &Projection, t: f32) -> Projection {
+    match (from, to) {
+        (Projection::Perspective(from), Projection::Perspective(to)) => {
+            Projection::Perspective(PerspectiveProjection {
+                fov: from.fov + (to.fov - from.fov) * t,
+                ..to.clone()
+            })
+        }
+        (Projection::Orthographic(from), Projection::Orthographic(to)) => {
+            Projection::Orthographic(OrthographicProjection {
+                scale: from.scale + (to.scale - from.scale) * t,
+                ..to.clone()
+            })
+        }
+        _ if t < 1.0 => from.clone(),
+        _ => to.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASINGS: [Prank3dEasing; 8] = [
+        Prank3dEasing::Linear,
+        Prank3dEasing::QuadraticIn,
+        Prank3dEasing::QuadraticOut,
+        Prank3dEasing::QuadraticInOut,
+        Prank3dEasing::CubicIn,
+        Prank3dEasing::CubicOut,
+        Prank3dEasing::CubicInOut,
+        Prank3dEasing::SmoothStep,
+    ];
+
+    /// Evenly spaced progress from zero to one.
+    fn samples() -> impl Iterator<Item = f32> {
+        (0..=100).map(|step| step as f32 / 100.0)
+    }
+
+    #[test]
+    fn ease_endpoints() {
+        for easing in EASINGS {
+            assert_eq!(easing.ease(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.ease(1.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn ease_clamps() {
+        for easing in EASINGS {
+            assert_eq!(easing.ease(-1.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.ease(2.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn ease_is_monotonic() {
+        for easing in EASINGS {
+            let values = samples().map(|t| easing.ease(t)).collect::<Vec<_>>();
+            for pair in values.windows(2) {
+                assert!(pair[0] <= pair[1], "{:?} decreases", easing);
+            }
+        }
+    }
+
+    #[test]
+    fn in_out_easings_are_symmetric() {
+        for easing in [
+            Prank3dEasing::Linear,
+            Prank3dEasing::QuadraticInOut,
+            Prank3dEasing::CubicInOut,
+            Prank3dEasing::SmoothStep,
+        ] {
+            for t in samples() {
+                let sum = easing.ease(t) + easing.ease(1.0 - t);
+                assert!((sum - 1.0).abs() < 1e-5, "{:?} at {}", easing, t);
+            }
+        }
+    }
+
+    fn perspective(fov: f32) -> Projection {
+        Projection::Perspective(PerspectiveProjection { fov, ..default() })
+    }
+
+    fn orthographic(scale: f32) -> Projection {
+        Projection::Orthographic(OrthographicProjection { scale, ..default() })
+    }
+
+    fn fov(projection: &Projection) -> f32 {
+        match projection {
+            Projection::Perspective(projection) => projection.fov,
+            Projection::Orthographic(_) => panic!("expected perspective projection"),
+        }
+    }
+
+    fn scale(projection: &Projection) -> f32 {
+        match projection {
+            Projection::Orthographic(projection) => projection.scale,
+            Projection::Perspective(_) => panic!("expected orthographic projection"),
+        }
+    }
+
+    #[test]
+    fn blend_perspective_fov() {
+        let (from, to) = (perspective(0.5), perspective(1.5));
+
+        assert_eq!(fov(&blend_projection(&from, &to, 0.0)), 0.5);
+        assert_eq!(fov(&blend_projection(&from, &to, 1.0)), 1.5);
+
+        let values = samples()
+            .map(|t| fov(&blend_projection(&from, &to, t)))
+            .collect::<Vec<_>>();
+        for pair in values.windows(2) {
+            assert!(pair[0] <= pair[1]);
+        }
+    }
+
+    #[test]
+    fn blend_orthographic_scale() {
+        let (from, to) = (orthographic(4.0), orthographic(1.0));
+
+        assert_eq!(scale(&blend_projection(&from, &to, 0.0)), 4.0);
+        assert_eq!(scale(&blend_projection(&from, &to, 1.0)), 1.0);
+
+        let values = samples()
+            .map(|t| scale(&blend_projection(&from, &to, t)))
+            .collect::<Vec<_>>();
+        for pair in values.windows(2) {
+            assert!(pair[0] >= pair[1]);
+        }
+    }
+
+    #[test]
+    fn blend_different_kinds_switches_at_end() {
+        let (from, to) = (perspective(1.0), orthographic(2.0));
+
+        assert_eq!(fov(&blend_projection(&from, &to, 0.0)), 1.0);
+        assert_eq!(fov(&blend_projection(&from, &to, 0.99)), 1.0);
+        assert_eq!(scale(&blend_projection(&from, &to, 1.0)), 2.0);
+    }
+}
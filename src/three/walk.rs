@@ -0,0 +1,286 @@
+use super::{
+    collision::Prank3dColliderBackend, look, PendingLook, Prank3d, Prank3dActive, Prank3dState,
+};
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+pub(super) struct Prank3dWalkPlugin;
+
+impl Plugin for Prank3dWalkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Walk>()
+            .add_systems(OnEnter(Prank3dState::Walk), reset);
+    }
+}
+
+#[derive(Default, Resource)]
+pub(super) struct Walk {
+    vertical_speed: f32,
+    grounded: bool,
+}
+
+fn reset(mut walk: ResMut<Walk>) {
+    *walk = Walk::default();
+}
+
+/// Keys that move the [`Prank3d`] while walking.
+const WALK_KEYS: [KeyCode; 5] = [
+    KeyCode::W,
+    KeyCode::A,
+    KeyCode::S,
+    KeyCode::D,
+    KeyCode::Space,
+];
+
+/// Whether any key that moves the [`Prank3d`] while walking is pressed.
+pub(super) fn is_moving(keyboard: &Input<KeyCode>) -> bool {
+    keyboard.any_pressed(WALK_KEYS)
+}
+
+pub(super) fn walk(
+    active: Res<Prank3dActive>,
+    mut pranks: Query<(&mut Transform, &mut Prank3d)>,
+    mut walk: ResMut<Walk>,
+    time: Res<Time>,
+    mut motion: EventReader<MouseMotion>,
+    keyboard: Res<Input<KeyCode>>,
+    mut pending_look: ResMut<PendingLook>,
+) {
+    let (mut transform, mut prank) = pranks.get_mut(active.expect("is active")).expect("exists");
+    let motion = motion.iter().fold(Vec2::ZERO, |acc, m| acc + m.delta);
+    let forward = Vec3::new(transform.forward().x, 0.0, transform.forward().z).normalize_or_zero();
+    let right = Vec3::new(transform.right().x, 0.0, transform.right().z).normalize_or_zero();
+    let mut movement = Vec3::ZERO;
+    if keyboard.pressed(KeyCode::W) {
+        movement += forward;
+    }
+    if keyboard.pressed(KeyCode::A) {
+        movement -= right;
+    }
+    if keyboard.pressed(KeyCode::S) {
+        movement -= forward;
+    }
+    if keyboard.pressed(KeyCode::D) {
+        movement += right;
+    }
+    if walk.grounded && keyboard.just_pressed(KeyCode::Space) {
+        walk.vertical_speed = prank.jump_speed;
+        walk.grounded = false;
+    }
+
+    let speed = prank.speed_modifier(&keyboard) * prank.walk_speed;
+    prank.translation += speed * movement.normalize_or_zero() * time.delta_seconds();
+
+    look(
+        &mut transform,
+        &prank,
+        &mut pending_look,
+        motion,
+        time.delta_seconds(),
+    );
+}
+
+/// Applies gravity to the [`Prank3d`] and keeps it at `eye_height` above the ground.
+pub(super) fn fall(world: &mut World) {
+    let entity = world.resource::<Prank3dActive>().expect("is active");
+    let delta_seconds = world.resource::<Time>().delta_seconds();
+    let prank = world.get::<Prank3d>(entity).expect("exists");
+    let (translation, eye_height, gravity) = (prank.translation, prank.eye_height, prank.gravity);
+
+    let ray = Ray {
+        origin: translation,
+        direction: Vec3::NEG_Y,
+    };
+    let ground = world
+        .resource::<Prank3dColliderBackend>()
+        .cast_ray(world, ray, f32::INFINITY, entity)
+        .map(|hit| translation.y - hit.distance);
+
+    let mut walk = world.resource_mut::<Walk>();
+    walk.vertical_speed -= gravity * delta_seconds;
+    let y = translation.y + walk.vertical_speed * delta_seconds;
+    let y = match ground {
+        Some(ground) if y <= ground + eye_height => {
+            walk.vertical_speed = 0.0;
+            walk.grounded = true;
+            ground + eye_height
+        }
+        _ => {
+            walk.grounded = false;
+            y
+        }
+    };
+
+    world
+        .get_mut::<Prank3d>(entity)
+        .expect("exists")
+        .translation
+        .y = y;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::three::collision::{Prank3dCollider, Prank3dHit};
+    use bevy::utils::{Duration, Instant};
+
+    const DELTA_SECONDS: f32 = 1.0 / 60.0;
+
+    /// Infinite floor at zero height.
+    struct FloorCollider;
+
+    impl Prank3dCollider for FloorCollider {
+        fn cast_sphere(
+            &self,
+            _world: &World,
+            ray: Ray,
+            max_distance: f32,
+            radius: f32,
+            _exclude: Entity,
+        ) -> Option<Prank3dHit> {
+            let distance = ray.intersect_plane(Vec3::Y * radius, Vec3::Y)?;
+            (distance <= max_distance).then_some(Prank3dHit {
+                entity: Entity::PLACEHOLDER,
+                distance,
+                normal: Vec3::Y,
+            })
+        }
+    }
+
+    struct Simulation {
+        world: World,
+        entity: Entity,
+        instant: Instant,
+    }
+
+    impl Simulation {
+        fn new(height: f32) -> Self {
+            let mut world = World::new();
+            let entity = world
+                .spawn((
+                    Transform::from_xyz(0.0, height, 0.0),
+                    Prank3d {
+                        translation: Vec3::new(0.0, height, 0.0),
+                        ..default()
+                    },
+                ))
+                .id();
+
+            let mut time = Time::default();
+            let instant = time.startup();
+            time.update_with_instant(instant);
+
+            world.insert_resource(time);
+            world.insert_resource(Prank3dActive(Some(entity)));
+            world.insert_resource(Prank3dColliderBackend::new(FloorCollider));
+            world.insert_resource(Walk::default());
+            world.insert_resource(PendingLook::default());
+            world.insert_resource(Input::<KeyCode>::default());
+            world.insert_resource(Events::<MouseMotion>::default());
+
+            Self {
+                world,
+                entity,
+                instant,
+            }
+        }
+
+        /// Runs a single frame of walk mode.
+        fn step(&mut self) {
+            self.instant += Duration::from_secs_f32(DELTA_SECONDS);
+            self.world
+                .resource_mut::<Time>()
+                .update_with_instant(self.instant);
+
+            let mut walk_system = IntoSystem::into_system(walk);
+            walk_system.initialize(&mut self.world);
+            walk_system.run((), &mut self.world);
+            fall(&mut self.world);
+
+            self.world.resource_mut::<Input<KeyCode>>().clear();
+        }
+
+        fn steps(&mut self, count: usize) {
+            for _ in 0..count {
+                self.step();
+            }
+        }
+
+        fn height(&self) -> f32 {
+            self.world
+                .get::<Prank3d>(self.entity)
+                .expect("exists")
+                .translation
+                .y
+        }
+
+        fn grounded(&self) -> bool {
+            self.world.resource::<Walk>().grounded
+        }
+
+        fn eye_height(&self) -> f32 {
+            self.world
+                .get::<Prank3d>(self.entity)
+                .expect("exists")
+                .eye_height
+        }
+    }
+
+    #[test]
+    fn falls_to_eye_height() {
+        let mut simulation = Simulation::new(10.0);
+        simulation.step();
+        assert!(simulation.height() < 10.0);
+        assert!(!simulation.grounded());
+
+        simulation.steps(120);
+        assert_eq!(simulation.height(), simulation.eye_height());
+        assert!(simulation.grounded());
+    }
+
+    #[test]
+    fn jumps_and_lands() {
+        let mut simulation = Simulation::new(Prank3d::default().eye_height);
+        simulation.step();
+        let eye_height = simulation.eye_height();
+        assert_eq!(simulation.height(), eye_height);
+        assert!(simulation.grounded());
+
+        simulation
+            .world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Space);
+        simulation.step();
+        assert!(simulation.height() > eye_height);
+        assert!(!simulation.grounded());
+
+        let apex = {
+            let prank = simulation
+                .world
+                .get::<Prank3d>(simulation.entity)
+                .expect("exists");
+            eye_height + prank.jump_speed.powi(2) / (2.0 * prank.gravity)
+        };
+        let mut peak = simulation.height();
+        for _ in 0..120 {
+            simulation.step();
+            peak = peak.max(simulation.height());
+        }
+        assert!((peak - apex).abs() < 0.1, "peak {} apex {}", peak, apex);
+
+        assert_eq!(simulation.height(), eye_height);
+        assert!(simulation.grounded());
+    }
+
+    #[test]
+    fn jumps_only_when_grounded() {
+        let mut simulation = Simulation::new(10.0);
+        simulation.step();
+
+        simulation
+            .world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Space);
+        simulation.step();
+        assert!(simulation.world.resource::<Walk>().vertical_speed < 0.0);
+    }
+}